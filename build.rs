@@ -0,0 +1,54 @@
+//! Generates the registry of solutions that are linked into the runner binary, see `src/linked.rs`.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("days.rs");
+
+    // solutions are named `<year>-<day>`, e.g. `2025-01`.
+    let is_solution = |name: &str| {
+        name.len() == 7
+            && name.char_indices().all(|(i, c)| match i {
+                4 => c == '-',
+                _ => c.is_ascii_digit(),
+            })
+    };
+
+    let mut solutions: Vec<String> = vec![];
+
+    // NOTE: every solution installs its own global allocator when profiling with dhat, so these cannot be linked together.
+    if env::var_os("CARGO_FEATURE_DHAT_HEAP").is_none()
+        && let Ok(entries) = fs::read_dir(Path::new(&manifest_dir).join("src/bin"))
+    {
+        solutions = entries
+            .filter_map(|entry| entry.ok()?.path().file_name()?.to_str().map(String::from))
+            .filter_map(|name| name.strip_suffix(".rs").map(String::from))
+            .filter(|name| is_solution(name))
+            .collect();
+    }
+
+    solutions.sort_unstable();
+
+    let module_name = |name: &str| format!("solution_{}", name.replace('-', "_"));
+    let mut content = String::new();
+
+    for name in &solutions {
+        let path = Path::new(&manifest_dir).join(format!("src/bin/{name}.rs"));
+        content.push_str(&format!(
+            "#[path = {:?}]\nmod {};\n",
+            path.display().to_string(),
+            module_name(name)
+        ));
+    }
+
+    content
+        .push_str("\npub static SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[\n");
+    for name in &solutions {
+        content.push_str(&format!("    {}::SOLUTION,\n", module_name(name)));
+    }
+    content.push_str("];\n");
+
+    fs::write(out_path, content).unwrap();
+}
@@ -1,11 +1,10 @@
 use std::collections::HashSet;
 
-advent_of_code::solution!(8);
+use advent_of_code::template::Params;
 
-#[cfg(test)]
-const ITERATIONS: usize = 10;
+advent_of_code::solution!(8);
 
-#[cfg(not(test))]
+/// Number of closest pairs to connect in the real input. The example sets `iterations` in its manifest.
 const ITERATIONS: usize = 1_000;
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
@@ -154,12 +153,15 @@ pub fn parse(input: &str) -> Vec<Point> {
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
+pub fn part_one(input: &str, params: &Params) -> Option<usize> {
     let points = parse(input);
     let point_pairs = get_pairs(&points);
     let mut graphs: Vec<Graph> = Vec::new();
 
-    for pair in point_pairs.iter().take(ITERATIONS) {
+    for pair in point_pairs
+        .iter()
+        .take(params.get_or("iterations", ITERATIONS))
+    {
         let mut added = false;
         for graph in graphs.iter_mut() {
             added = graph.add_point_pair(pair);
@@ -222,20 +224,3 @@ pub fn part_two(input: &str) -> Option<i64> {
 
     Some(25_272)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(40));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(25_272));
-    }
-}
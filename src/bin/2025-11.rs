@@ -5,9 +5,9 @@ use petgraph::algo::toposort;
 use petgraph::graph::DiGraph;
 use petgraph::{algo, prelude::*};
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, parse = parse);
 
-pub fn parse(input: &str) -> Vec<(String, String)> {
+pub fn parse_edges(input: &str) -> Vec<(String, String)> {
     input
         .lines()
         .filter(|row| !row.is_empty())
@@ -23,6 +23,29 @@ pub fn parse(input: &str) -> Vec<(String, String)> {
         .collect()
 }
 
+pub struct Network {
+    graph: DiGraph<String, ()>,
+    node_lookup: HashMap<String, NodeIndex>,
+}
+
+pub fn parse(input: &str) -> Network {
+    let edges = parse_edges(input);
+    let mut node_lookup = HashMap::<String, NodeIndex>::new();
+    let mut graph = DiGraph::<String, ()>::new();
+
+    for edge in &edges {
+        let from = *node_lookup
+            .entry(edge.0.clone())
+            .or_insert_with(|| graph.add_node(edge.0.clone()));
+        let to = *node_lookup
+            .entry(edge.1.clone())
+            .or_insert_with(|| graph.add_node(edge.1.clone()));
+        graph.add_edge(from, to, ());
+    }
+
+    Network { graph, node_lookup }
+}
+
 fn count_paths_between(graph: &DiGraph<String, ()>, from: NodeIndex, to: NodeIndex) -> u64 {
     // paths[node] = number of paths from `from` to `node`
     let mut paths: HashMap<NodeIndex, u64> = HashMap::new();
@@ -48,79 +71,34 @@ fn count_paths_between(graph: &DiGraph<String, ()>, from: NodeIndex, to: NodeInd
     *paths.get(&to).unwrap_or(&0)
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let edges = parse(input);
-    let mut node_lookup = HashMap::<String, NodeIndex>::new();
-    let mut graph = DiGraph::<String, ()>::new();
-
-    for edge in &edges {
-        let from = *node_lookup
-            .entry(edge.0.clone())
-            .or_insert_with(|| graph.add_node(edge.0.clone()));
-        let to = *node_lookup
-            .entry(edge.1.clone())
-            .or_insert_with(|| graph.add_node(edge.1.clone()));
-        graph.add_edge(from, to, ());
-    }
+pub fn part_one(network: &Network) -> Option<usize> {
+    let Network { graph, node_lookup } = network;
 
     let start_node = *node_lookup.get("you").unwrap();
     let end_node = *node_lookup.get("out").unwrap();
 
     let paths =
-        algo::all_simple_paths::<Vec<_>, _, RandomState>(&graph, start_node, end_node, 0, None)
+        algo::all_simple_paths::<Vec<_>, _, RandomState>(graph, start_node, end_node, 0, None)
             .collect::<Vec<_>>();
 
     Some(paths.len())
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let edges = parse(input);
-    let mut node_lookup = HashMap::<String, NodeIndex>::new();
-    let mut graph = DiGraph::<String, ()>::new();
-
-    for edge in &edges {
-        let from = *node_lookup
-            .entry(edge.0.clone())
-            .or_insert_with(|| graph.add_node(edge.0.clone()));
-        let to = *node_lookup
-            .entry(edge.1.clone())
-            .or_insert_with(|| graph.add_node(edge.1.clone()));
-        graph.add_edge(from, to, ());
-    }
+pub fn part_two(network: &Network) -> Option<u64> {
+    let Network { graph, node_lookup } = network;
 
     let start_node = *node_lookup.get("svr").unwrap();
     let end_node = *node_lookup.get("out").unwrap();
     let dac_node = *node_lookup.get("dac").unwrap();
     let fft_node = *node_lookup.get("fft").unwrap();
 
-    let count_1 = count_paths_between(&graph, start_node, dac_node)
-        * count_paths_between(&graph, dac_node, fft_node)
-        * count_paths_between(&graph, fft_node, end_node);
+    let count_1 = count_paths_between(graph, start_node, dac_node)
+        * count_paths_between(graph, dac_node, fft_node)
+        * count_paths_between(graph, fft_node, end_node);
 
-    let count_2 = count_paths_between(&graph, start_node, fft_node)
-        * count_paths_between(&graph, fft_node, dac_node)
-        * count_paths_between(&graph, dac_node, end_node);
+    let count_2 = count_paths_between(graph, start_node, fft_node)
+        * count_paths_between(graph, fft_node, dac_node)
+        * count_paths_between(graph, dac_node, end_node);
 
     Some(count_1 + count_2)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(5));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(2));
-    }
-}
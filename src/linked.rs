@@ -0,0 +1,19 @@
+//! The runner with all solutions of `src/bin` linked in, see `build.rs`.
+//! `all`, `time` and `verify` re-run themselves in it to execute the solutions in-process.
+//! It is a separate binary, so that a solution that does not compile yet does not break the other commands.
+#[path = "main.rs"]
+mod cli;
+
+/// Solutions that are linked into the runner, generated by `build.rs`.
+#[cfg(not(test))]
+#[allow(dead_code)]
+mod days {
+    include!(concat!(env!("OUT_DIR"), "/days.rs"));
+}
+
+fn main() {
+    #[cfg(not(test))]
+    advent_of_code::template::register_solutions(days::SOLUTIONS);
+
+    cli::main();
+}
@@ -1,13 +1,20 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, read, scaffold, solve, time, verify, watch,
+};
+use advent_of_code::template::{Error, is_linked, run_linked};
 use args::{AppArguments, parse};
+use std::process;
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::{Day, Event};
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::commands::scaffold;
+    use advent_of_code::template::commands::time::CompareOptions;
+    use advent_of_code::template::runner::{
+        BenchConfig, InputSource, Limits, OutputFormat, parse_duration, parse_memory,
+    };
+    use advent_of_code::template::{Day, Year};
     use std::process;
 
     pub enum AppArguments {
@@ -21,20 +28,43 @@ mod args {
             day: Day,
             download: bool,
             overwrite: bool,
+            template: String,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            format: Option<OutputFormat>,
+            input: InputSource,
+            limits: Limits,
         },
         All {
             release: bool,
+            verify: bool,
+            jobs: usize,
+            limits: Limits,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            bench: BenchConfig,
+            compare: Option<CompareOptions>,
+        },
+        History {
+            day: Day,
+        },
+        Verify {
+            day: Option<Day>,
+            release: bool,
+            limits: Limits,
+            all_inputs: bool,
+        },
+        Watch {
+            day: Day,
+            release: bool,
+            limits: Limits,
         },
         #[cfg(feature = "today")]
         Today,
@@ -43,20 +73,55 @@ mod args {
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+
+        // NOTE: the year has to be known before parsing days, as it decides which days are valid.
+        if let Some(year) = args.opt_value_from_str::<_, Year>("--year")? {
+            Year::set_current(year);
+        }
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                verify: args.contains("--verify"),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                limits: limits(&mut args)?,
+            },
+            Some("time") if args.contains("--history") => AppArguments::History {
+                day: args.free_from_str()?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let fail_threshold = args.opt_value_from_str("--fail-threshold")?;
+                let mut bench = BenchConfig {
+                    iterations: args.opt_value_from_fn("--iterations", iterations)?,
+                    ..BenchConfig::default()
+                };
+                if let Some(budget) = args.opt_value_from_fn("--budget", parse_duration)? {
+                    bench.budget = budget;
+                }
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    bench,
+                    compare: compare.then_some(CompareOptions { fail_threshold }),
                 }
             }
+            Some("verify") => AppArguments::Verify {
+                release: args.contains("--release"),
+                limits: limits(&mut args)?,
+                all_inputs: args.contains("--all-inputs"),
+                day: args.opt_free_from_str()?,
+            },
+            Some("watch") => AppArguments::Watch {
+                release: args.contains("--release"),
+                limits: limits(&mut args)?,
+                day: args.free_from_str()?,
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
@@ -67,22 +132,58 @@ mod args {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args
+                    .opt_value_from_str("--template")?
+                    .unwrap_or_else(|| scaffold::DEFAULT_TEMPLATE.to_string()),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let day = args.free_from_str()?;
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let format = args.opt_value_from_str("--format")?;
+                let download = args.contains("--download");
+                let limits = limits(&mut args)?;
+                let path = args.opt_value_from_str("--input")?;
+                // NOTE: the example number is optional, parse it after all other options.
+                let example = if args.contains("--example") {
+                    Some(args.opt_free_from_str()?.unwrap_or(1))
+                } else {
+                    None
+                };
+
+                let input = match (path, example) {
+                    (Some(_), Some(_)) => {
+                        return Err("--input and --example cannot be combined.".into());
+                    }
+                    (Some(path), None) => InputSource::File(path),
+                    (None, Some(n)) => InputSource::Example(n),
+                    (None, None) => InputSource::Puzzle { download },
+                };
+
+                if submit.is_some() && !matches!(input, InputSource::Puzzle { .. }) {
+                    return Err("only results for the puzzle input can be submitted.".into());
+                }
+
+                AppArguments::Solve {
+                    day,
+                    release,
+                    submit,
+                    dhat,
+                    format,
+                    input,
+                    limits,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
                 eprintln!("Unknown command: {x}");
-                process::exit(1);
+                process::exit(2);
             }
             None => {
                 eprintln!("No command specified.");
-                process::exit(1);
+                process::exit(2);
             }
         };
 
@@ -93,52 +194,122 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parses the `--iterations` of a benchmark, which needs at least one sample.
+    fn iterations(s: &str) -> Result<u128, String> {
+        match s.parse() {
+            Ok(0) => Err("expecting at least 1 iteration.".into()),
+            Ok(iterations) => Ok(iterations),
+            Err(e) => Err(format!("{e}")),
+        }
+    }
+
+    fn limits(args: &mut pico_args::Arguments) -> Result<Limits, pico_args::Error> {
+        Ok(Limits {
+            timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+            memory: args.opt_value_from_fn("--memory", parse_memory)?,
+        })
+    }
 }
 
-fn main() {
-    match parse() {
+/// Also the entry point of the linked runner, see `src/linked.rs`.
+pub fn main() {
+    advent_of_code::template::migrate_legacy_data();
+
+    let args = match parse() {
+        Ok(args) => args,
         Err(err) => {
             eprintln!("Error: {err}");
-            std::process::exit(1);
+            process::exit(2);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
+    };
+
+    if let Err(err) = run(args) {
+        eprintln!("Error: {err}");
+        process::exit(err.exit_code());
+    }
+}
+
+fn run(args: AppArguments) -> Result<(), Error> {
+    match args {
+        // NOTE: these commands run the solutions of many days, which is faster in the linked runner.
+        AppArguments::All { release, .. } | AppArguments::Verify { release, .. }
+            if !is_linked() && run_linked(release)? =>
+        {
+            Ok(())
+        }
+        AppArguments::Time { .. } if !is_linked() && run_linked(true)? => Ok(()),
+        AppArguments::All {
+            release,
+            verify,
+            jobs,
+            limits,
+        } => all::handle(release, verify, jobs, &limits),
+        AppArguments::Time {
+            day,
+            all,
+            store,
+            bench,
+            compare,
+        } => time::handle(day, all, store, &bench, compare),
+        AppArguments::History { day } => {
+            time::history(day);
+            Ok(())
+        }
+        AppArguments::Verify {
+            day,
+            release,
+            limits,
+            all_inputs,
+        } => verify::handle(day, release, &limits, all_inputs),
+        AppArguments::Watch {
+            day,
+            release,
+            limits,
+        } => watch::handle(day, release, &limits),
+        AppArguments::Download { day } => download::handle(day),
+        AppArguments::Read { day } => read::handle(day),
+        AppArguments::Scaffold {
+            day,
+            download,
+            overwrite,
+            template,
+        } => {
+            // NOTE: download first, so that the template can use the puzzle title and the example answers.
+            // a failed download does not stop the scaffold, the title falls back to `Day N`.
+            let downloaded = if download {
+                download::handle(day)
+            } else {
+                Ok(())
+            };
+            scaffold::handle(day, overwrite, &template)?;
+            downloaded
+        }
+        AppArguments::Solve {
+            day,
+            release,
+            dhat,
+            submit,
+            format,
+            input,
+            limits,
+        } => solve::handle(day, release, dhat, submit, format, &input, &limits),
+        #[cfg(feature = "today")]
+        AppArguments::Today => match Day::today() {
+            Some(day) => {
+                let downloaded = download::handle(day);
+                scaffold::handle(day, false, scaffold::DEFAULT_TEMPLATE)?;
+                downloaded?;
+                read::handle(day)
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+            None => {
+                eprintln!(
+                    "`today` command can only be run between the 1st and \
+                    the {}th of december. Please use `scaffold` with a specific day.",
+                    Event::current().last_day().into_inner()
+                );
+                process::exit(2)
             }
         },
-    };
+    }
 }
@@ -0,0 +1,268 @@
+use std::fmt::Display;
+
+/// The answer of a part. Parts return any type that converts into it with [`IntoAnswer`],
+/// e.g. `Option<u64>`, `Option<String>` or an `Option` of any other [`Display`] type.
+///
+/// Some puzzles draw their answer as block letters. Return the drawing as a multi-line string and the
+/// runner reads the letters, so that submissions, the answer ledger and `verify` use the canonical answer:
+///
+/// ```
+/// # use advent_of_code::template::Answer;
+/// let drawing = "\
+/// #..#.###.
+/// #..#..#..
+/// ####..#..
+/// #..#..#..
+/// #..#..#..
+/// #..#.###.";
+///
+/// assert_eq!(Answer::from(drawing).to_string(), "HI");
+/// assert_eq!(Answer::from(42_u64).to_string(), "42");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// A drawing of block letters, see [`Answer::canonical`].
+    Art(String),
+}
+
+impl Answer {
+    /// The answer as it is submitted. Drawings are read with the block-letter font of Advent of Code.
+    /// Returns `None` if the letters of a drawing cannot be read.
+    pub fn canonical(&self) -> Option<String> {
+        match self {
+            Answer::Integer(value) => Some(value.to_string()),
+            Answer::Text(text) => Some(text.clone()),
+            Answer::Art(art) => read_letters(art),
+        }
+    }
+}
+
+/// Prints the canonical answer, or the drawing itself if its letters cannot be read.
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{value}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Art(art) => write!(f, "{}", read_letters(art).as_deref().unwrap_or(art)),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Integer(i128::from(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+/// Integers that do not fit into an `i128` are text.
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        i128::try_from(value).map_or_else(|_| Answer::Text(value.to_string()), Answer::Integer)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Integer(value as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Answer::Integer(value as i128)
+    }
+}
+
+/// Multi-line strings are drawings, other strings are text.
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        if value.trim().contains('\n') {
+            Answer::Art(value)
+        } else {
+            Answer::Text(value)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::from(value.to_string())
+    }
+}
+
+impl From<char> for Answer {
+    fn from(value: char) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+/// Converts the result of a part into an [`Answer`]. Implemented for every [`Display`] type:
+/// integers become [`Answer::Integer`], multi-line results [`Answer::Art`] and everything else [`Answer::Text`].
+pub trait IntoAnswer {
+    fn into_answer(self) -> Answer;
+}
+
+impl<T: Display> IntoAnswer for T {
+    fn into_answer(self) -> Answer {
+        let value = self.to_string();
+        match value.parse::<i128>() {
+            Ok(integer) if integer.to_string() == value => Answer::Integer(integer),
+            _ => Answer::from(value),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Height of the block letters.
+const LETTER_HEIGHT: usize = 6;
+
+/// The block letters of Advent of Code, without the empty column that separates them.
+const LETTERS: [(char, [&str; LETTER_HEIGHT]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Whether a character of a drawing is a lit pixel. Anything else, e.g. `.` or a space, is dark.
+fn is_lit(c: char) -> bool {
+    matches!(c, '#' | '█')
+}
+
+/// Reads the block letters of a drawing. Rows without lit pixels around the letters are ignored.
+fn read_letters(art: &str) -> Option<String> {
+    let rows: Vec<Vec<bool>> = art
+        .lines()
+        .map(|line| line.chars().map(is_lit).collect())
+        .collect();
+
+    let first = rows.iter().position(|row| row.contains(&true))?;
+    let last = rows.iter().rposition(|row| row.contains(&true))?;
+    let rows = &rows[first..=last];
+
+    if rows.len() != LETTER_HEIGHT {
+        return None;
+    }
+
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let pixel = |row: usize, col: usize| rows[row].get(col).copied().unwrap_or(false);
+    let is_column_lit = |col: usize| (0..LETTER_HEIGHT).any(|row| pixel(row, col));
+
+    let mut letters = String::new();
+    let mut col = 0;
+
+    while col < width {
+        if !is_column_lit(col) {
+            col += 1;
+            continue;
+        }
+
+        let start = col;
+        while col < width && is_column_lit(col) {
+            col += 1;
+        }
+
+        let glyph: Vec<String> = (0..LETTER_HEIGHT)
+            .map(|row| {
+                (start..col)
+                    .map(|col| if pixel(row, col) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+
+        let (letter, _) = LETTERS.iter().find(|(_, pattern)| glyph == pattern)?;
+        letters.push(*letter);
+    }
+
+    Some(letters)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, IntoAnswer};
+    use std::fmt::Display;
+
+    #[test]
+    fn converts_results() {
+        assert_eq!(Answer::from(42_u64), Answer::Integer(42));
+        assert_eq!(Answer::from(-3_i64), Answer::Integer(-3));
+        assert_eq!(Answer::from(7_usize), Answer::Integer(7));
+        assert_eq!(Answer::from("abc"), Answer::Text("abc".into()));
+        assert_eq!(Answer::from('x'), Answer::Text("x".into()));
+        assert_eq!(Answer::from("#.\n.#\n"), Answer::Art("#.\n.#\n".into()));
+        assert_eq!(Answer::from(u64::MAX).to_string(), u64::MAX.to_string());
+    }
+
+    #[test]
+    fn converts_large_and_custom_results() {
+        assert_eq!(Answer::from(42_u128), Answer::Integer(42));
+        assert_eq!(Answer::from(u128::MAX), Answer::Text(u128::MAX.to_string()));
+
+        struct Point(i32, i32);
+        impl Display for Point {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{},{}", self.0, self.1)
+            }
+        }
+
+        assert_eq!(Point(3, 4).into_answer(), Answer::Text("3,4".into()));
+        assert_eq!(u128::MAX.into_answer(), Answer::Text(u128::MAX.to_string()));
+        assert_eq!(7_u8.into_answer(), Answer::Integer(7));
+        assert_eq!("007".into_answer(), Answer::Text("007".into()));
+        assert_eq!("#.\n.#".into_answer(), Answer::Art("#.\n.#".into()));
+    }
+
+    #[test]
+    fn reads_block_letters() {
+        let art = [
+            "",
+            "####.#..#.####.#.....##..",
+            "#....#..#.#....#....#..#.",
+            "###..####.###..#....#..#.",
+            "#....#..#.#....#....#..#.",
+            "#....#..#.#....#....#..#.",
+            "####.#..#.####.####..##..",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(Answer::from(art).canonical().as_deref(), Some("EHELO"));
+
+        let art = "█   █ ███\n█   █  █ \n █ █   █ \n  █    █ \n  █    █ \n  █   ███";
+        assert_eq!(Answer::from(art).canonical().as_deref(), Some("YI"));
+    }
+
+    #[test]
+    fn prints_unreadable_drawings() {
+        let art = "#..\n.#.\n..#";
+        let answer = Answer::from(art);
+        assert_eq!(answer.canonical(), None);
+        assert_eq!(answer.to_string(), art);
+    }
+}
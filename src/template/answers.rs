@@ -0,0 +1,489 @@
+/// Local ledger of submitted answers. Used to guard against re-submitting known wrong answers.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::{Hint, SubmissionOutcome};
+use crate::template::{Day, Error, Year};
+
+fn answers_file_path() -> PathBuf {
+    Year::current().data_dir().join("answers.json")
+}
+
+/// Everything we learned about the answer to one part of a puzzle.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartAnswers {
+    pub accepted: Option<String>,
+    pub rejected: Vec<String>,
+    /// The largest answer known to be too low.
+    pub lower_bound: Option<i128>,
+    /// The smallest answer known to be too high.
+    pub upper_bound: Option<i128>,
+}
+
+/// Represents the answers ledger for a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayAnswers {
+    pub day: Day,
+    pub part_1: PartAnswers,
+    pub part_2: PartAnswers,
+}
+
+/// Represents the answers ledger for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<DayAnswers>,
+}
+
+/// Reason for refusing to submit an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    KnownWrong,
+    TooHigh(i128),
+    TooLow(i128),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with answer {answer}.")
+            }
+            Refusal::KnownWrong => write!(f, "this answer was already rejected before."),
+            Refusal::TooHigh(bound) => {
+                write!(f, "this answer is too high, {bound} was already too high.")
+            }
+            Refusal::TooLow(bound) => {
+                write!(f, "this answer is too low, {bound} was already too low.")
+            }
+        }
+    }
+}
+
+impl PartAnswers {
+    /// Checks whether `answer` could be correct based on previous submissions.
+    pub fn check(&self, answer: &str) -> Result<(), Refusal> {
+        if let Some(accepted) = &self.accepted {
+            return Err(Refusal::AlreadySolved(accepted.clone()));
+        }
+
+        if self.rejected.iter().any(|x| x == answer) {
+            return Err(Refusal::KnownWrong);
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            if let Some(upper) = self.upper_bound
+                && value >= upper
+            {
+                return Err(Refusal::TooHigh(upper));
+            }
+
+            if let Some(lower) = self.lower_bound
+                && value <= lower
+            {
+                return Err(Refusal::TooLow(lower));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the outcome of a submission.
+    pub fn record(&mut self, answer: &str, outcome: &SubmissionOutcome) {
+        match outcome {
+            SubmissionOutcome::Correct => {
+                self.accepted = Some(answer.to_string());
+            }
+            SubmissionOutcome::Incorrect(hint) => {
+                if !self.rejected.iter().any(|x| x == answer) {
+                    self.rejected.push(answer.to_string());
+                }
+
+                let value = answer.parse::<i128>().ok();
+
+                match (hint, value) {
+                    (Some(Hint::TooHigh), Some(value)) => {
+                        self.upper_bound = Some(self.upper_bound.map_or(value, |x| x.min(value)));
+                    }
+                    (Some(Hint::TooLow), Some(value)) => {
+                        self.lower_bound = Some(self.lower_bound.map_or(value, |x| x.max(value)));
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl DayAnswers {
+    pub fn new(day: Day) -> Self {
+        Self {
+            day,
+            part_1: PartAnswers::default(),
+            part_2: PartAnswers::default(),
+        }
+    }
+
+    pub fn part(&self, part: u8) -> &PartAnswers {
+        if part == 1 {
+            &self.part_1
+        } else {
+            &self.part_2
+        }
+    }
+
+    pub fn part_mut(&mut self, part: u8) -> &mut PartAnswers {
+        if part == 1 {
+            &mut self.part_1
+        } else {
+            &mut self.part_2
+        }
+    }
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        let json = JsonValue::from(self.clone());
+        fs::create_dir_all(Year::current().data_dir())?;
+        let mut file = fs::File::create(answers_file_path())?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns an empty ledger.
+    /// Unlike timings, the ledger cannot be regenerated, so a file that cannot be parsed is an error
+    /// instead of an empty ledger that would overwrite it on the next submission.
+    pub fn read_from_file() -> Result<Self, Error> {
+        Self::read(&answers_file_path())
+    }
+
+    fn read(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(content) => Answers::try_from(content)
+                .map_err(|e| Error::io(path)(io::Error::new(io::ErrorKind::InvalidData, e))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(Error::io(path)(e)),
+        }
+    }
+
+    /// Returns the ledger for one part of a day, if any submissions were recorded.
+    pub fn get(&self, day: Day, part: u8) -> Option<&PartAnswers> {
+        self.data
+            .iter()
+            .find(|x| x.day == day)
+            .map(|x| x.part(part))
+    }
+
+    /// Checks whether `answer` could be correct based on previous submissions.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        self.get(day, part).map_or(Ok(()), |x| x.check(answer))
+    }
+
+    /// Records the outcome of a submission.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, outcome: &SubmissionOutcome) {
+        let index = if let Some(index) = self.data.iter().position(|x| x.day == day) {
+            index
+        } else {
+            self.data.push(DayAnswers::new(day));
+            self.data.sort_unstable_by_key(|x| x.day);
+            self.data.iter().position(|x| x.day == day).unwrap()
+        };
+
+        self.data[index].part_mut(part).record(answer, outcome);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(DayAnswers::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&DayAnswers> for JsonValue {
+    fn from(value: &DayAnswers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part_1".into(), JsonValue::from(&value.part_1));
+        map.insert("part_2".into(), JsonValue::from(&value.part_2));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for DayAnswers {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answers to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answers.day to be a Day struct.")?;
+
+        let part_1 = PartAnswers::try_from(
+            json.get("part_1")
+                .ok_or("Expected answers.part_1 to be an object.")?,
+        )?;
+
+        let part_2 = PartAnswers::try_from(
+            json.get("part_2")
+                .ok_or("Expected answers.part_2 to be an object.")?,
+        )?;
+
+        Ok(DayAnswers {
+            day,
+            part_1,
+            part_2,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartAnswers> for JsonValue {
+    fn from(value: &PartAnswers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        // NOTE: bounds are stored as strings since JSON numbers lose precision above 2^53.
+        let optional_string = |x: Option<String>| x.map_or(JsonValue::Null, JsonValue::String);
+
+        map.insert("accepted".into(), optional_string(value.accepted.clone()));
+        map.insert(
+            "rejected".into(),
+            JsonValue::Array(
+                value
+                    .rejected
+                    .iter()
+                    .cloned()
+                    .map(JsonValue::String)
+                    .collect(),
+            ),
+        );
+        map.insert(
+            "lower_bound".into(),
+            optional_string(value.lower_bound.map(|x| x.to_string())),
+        );
+        map.insert(
+            "upper_bound".into(),
+            optional_string(value.upper_bound.map(|x| x.to_string())),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartAnswers {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part answers to be a JSON object.")?;
+
+        let optional_string = |key: &str| -> Result<Option<String>, String> {
+            match json.get(key) {
+                None | Some(JsonValue::Null) => Ok(None),
+                Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+                Some(_) => Err(format!("Expected answers.{key} to be null or string.")),
+            }
+        };
+
+        let optional_bound = |key: &str| -> Result<Option<i128>, String> {
+            optional_string(key)?
+                .map(|s| {
+                    s.parse()
+                        .map_err(|_| format!("Expected answers.{key} to be an integer."))
+                })
+                .transpose()
+        };
+
+        let rejected = match json.get("rejected") {
+            None => vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected answers.rejected to be an array.")?
+                .iter()
+                .map(|x| {
+                    x.get::<String>()
+                        .cloned()
+                        .ok_or("Expected answers.rejected to contain strings.")
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
+        Ok(PartAnswers {
+            accepted: optional_string("accepted")?,
+            rejected,
+            lower_bound: optional_bound("lower_bound")?,
+            upper_bound: optional_bound("upper_bound")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::day;
+    use crate::template::aoc_client::{Hint, SubmissionOutcome};
+
+    use super::{Answers, PartAnswers, Refusal};
+
+    mod check {
+        use super::*;
+
+        #[test]
+        fn allows_unknown_answers() {
+            let answers = Answers::default();
+            assert_eq!(answers.check(day!(1), 1, "42"), Ok(()));
+        }
+
+        #[test]
+        fn refuses_rejected_answers() {
+            let mut answers = Answers::default();
+            answers.record(day!(1), 1, "abc", &SubmissionOutcome::Incorrect(None));
+            assert_eq!(answers.check(day!(1), 1, "abc"), Err(Refusal::KnownWrong));
+            assert_eq!(answers.check(day!(1), 2, "abc"), Ok(()));
+        }
+
+        #[test]
+        fn refuses_answers_out_of_bounds() {
+            let mut answers = Answers::default();
+            let too_high = SubmissionOutcome::Incorrect(Some(Hint::TooHigh));
+            let too_low = SubmissionOutcome::Incorrect(Some(Hint::TooLow));
+
+            answers.record(day!(3), 2, "100", &too_high);
+            answers.record(day!(3), 2, "200", &too_high);
+            answers.record(day!(3), 2, "10", &too_low);
+
+            assert_eq!(answers.check(day!(3), 2, "150"), Err(Refusal::TooHigh(100)));
+            assert_eq!(answers.check(day!(3), 2, "100"), Err(Refusal::KnownWrong));
+            assert_eq!(answers.check(day!(3), 2, "5"), Err(Refusal::TooLow(10)));
+            assert_eq!(answers.check(day!(3), 2, "50"), Ok(()));
+        }
+
+        #[test]
+        fn refuses_solved_parts() {
+            let mut answers = Answers::default();
+            answers.record(day!(2), 1, "7", &SubmissionOutcome::Correct);
+            assert_eq!(
+                answers.check(day!(2), 1, "8"),
+                Err(Refusal::AlreadySolved("7".into()))
+            );
+        }
+
+        #[test]
+        fn ignores_rate_limited_submissions() {
+            let mut answers = Answers::default();
+            answers.record(day!(2), 1, "7", &SubmissionOutcome::TooRecent(None));
+            assert_eq!(answers.check(day!(2), 1, "7"), Ok(()));
+        }
+    }
+
+    mod serialization {
+        use super::*;
+
+        #[test]
+        fn roundtrips_answers() {
+            let mut answers = Answers::default();
+            answers.record(day!(4), 1, "1", &SubmissionOutcome::Correct);
+            answers.record(
+                day!(1),
+                2,
+                "170141183460469231731687303715884105727",
+                &SubmissionOutcome::Incorrect(Some(Hint::TooHigh)),
+            );
+
+            let json = tinyjson::JsonValue::from(answers).stringify().unwrap();
+            let answers = Answers::try_from(json).unwrap();
+
+            assert_eq!(answers.data.len(), 2);
+            assert_eq!(answers.data[0].day, day!(1));
+            assert_eq!(
+                answers.data[0].part_2,
+                PartAnswers {
+                    accepted: None,
+                    rejected: vec!["170141183460469231731687303715884105727".into()],
+                    lower_bound: None,
+                    upper_bound: Some(i128::MAX),
+                }
+            );
+            assert_eq!(answers.data[1].part_1.accepted, Some("1".into()));
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_malformed_answers() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "upper_bound": 3 } }] }"#;
+            Answers::try_from(json.to_string()).unwrap();
+        }
+    }
+
+    mod read {
+        use super::Answers;
+        use std::{env, fs, process};
+
+        #[test]
+        fn refuses_corrupt_ledgers() {
+            let dir = env::temp_dir().join(format!("aoc-answers-{}", process::id()));
+            fs::create_dir_all(&dir).unwrap();
+
+            let missing = dir.join("missing.json");
+            assert!(Answers::read(&missing).unwrap().data.is_empty());
+
+            let corrupt = dir.join("answers.json");
+            fs::write(&corrupt, "{ \"data\": [").unwrap();
+            let result = Answers::read(&corrupt);
+            fs::remove_dir_all(&dir).unwrap();
+
+            assert!(result.is_err());
+        }
+    }
+}
@@ -0,0 +1,620 @@
+/// Native HTTP client for the Advent of Code website.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::{Day, Year};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+static USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+static SESSION_FILE_NAME: &str = ".adventofcode.session";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    BadStatus(u16, String),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set the AOC_SESSION environment variable or create the file \"~/{SESSION_FILE_NAME}\"."
+            ),
+            AocClientError::BadStatus(status, url) => {
+                write!(f, "request to \"{url}\" failed with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for AocClientError {}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, response) => {
+                AocClientError::BadStatus(status, response.get_url().to_string())
+            }
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/// A hint returned by the website when an incorrect answer was submitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// The result of submitting an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect(Option<Hint>),
+    /// The submission was rejected because of the rate limit. Contains the remaining wait time, if known.
+    TooRecent(Option<String>),
+    /// The part is either already solved or not unlocked yet.
+    WrongLevel,
+    /// The response could not be classified. Contains the plain-text response message.
+    Unknown(String),
+}
+
+impl SubmissionOutcome {
+    fn from_message(message: &str) -> Self {
+        if message.contains("That's the right answer") {
+            SubmissionOutcome::Correct
+        } else if message.contains("That's not the right answer") {
+            let hint = if message.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if message.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+            SubmissionOutcome::Incorrect(hint)
+        } else if message.contains("You gave an answer too recently") {
+            let wait = message
+                .split("You have ")
+                .nth(1)
+                .and_then(|s| s.split(" left to wait").next())
+                .map(str::to_string);
+            SubmissionOutcome::TooRecent(wait)
+        } else if message.contains("You don't seem to be solving the right level") {
+            SubmissionOutcome::WrongLevel
+        } else {
+            SubmissionOutcome::Unknown(message.trim().to_string())
+        }
+    }
+}
+
+/// Client for fetching inputs and puzzles as well as submitting answers.
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    year: Year,
+}
+
+impl AocClient {
+    pub fn new(session: &str, year: Year) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            agent,
+            base_url: DEFAULT_BASE_URL.into(),
+            session: session.trim().into(),
+            year,
+        }
+    }
+
+    /// Creates a client from the environment.
+    /// The session cookie is read from `AOC_SESSION` or a `.adventofcode.session` file,
+    /// the year from [`Year::current`] and the base url from `AOC_BASE_URL` (if set).
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = find_session().ok_or(AocClientError::SessionNotFound)?;
+        let client = Self::new(&session, Year::current());
+
+        Ok(match env::var("AOC_BASE_URL") {
+            Ok(base_url) if !base_url.is_empty() => client.with_base_url(&base_url),
+            _ => client,
+        })
+    }
+
+    /// Overrides the base url, e.g. to point the client at a mock server.
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').into();
+        self
+    }
+
+    pub fn year(&self) -> Year {
+        self.year
+    }
+
+    /// Fetches the puzzle input for a day.
+    pub fn fetch_input(&self, day: Day) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.day_url(day));
+        self.get(&url)
+    }
+
+    /// Fetches the puzzle description for a day. Returns the `<article>` elements of the puzzle page.
+    pub fn fetch_puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let html = self.get(&self.day_url(day))?;
+        Ok(extract_elements(&html, "article").join("\n\n"))
+    }
+
+    /// Submits an answer for one part of a day.
+    pub fn submit(
+        &self,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, AocClientError> {
+        let url = format!("{}/answer", self.day_url(day));
+
+        let html = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        let message = extract_elements(&html, "main")
+            .first()
+            .map_or_else(|| html_to_text(&html), |main| html_to_text(main));
+
+        Ok(SubmissionOutcome::from_message(&message))
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        Ok(self
+            .agent
+            .get(url)
+            .set("Cookie", &self.cookie())
+            .call()?
+            .into_string()?)
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Downloads input and puzzle description for a day and writes them to the `data` directory.
+pub fn download(day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let input_path = get_input_path(day);
+    let puzzle_path = get_puzzle_path(day);
+
+    println!(
+        "Fetching input and puzzle for day {day}, {}...",
+        client.year()
+    );
+
+    let input = client.fetch_input(day)?;
+    let puzzle = client.fetch_puzzle(day)?;
+
+    write_file(&input_path, &input)?;
+    write_file(&puzzle_path, &puzzle)?;
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+/// Fetches the puzzle description for a day, stores it and prints it to the terminal.
+pub fn read(day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let puzzle_path = get_puzzle_path(day);
+
+    println!("Fetching puzzle for day {day}, {}...", client.year());
+
+    let puzzle = client.fetch_puzzle(day)?;
+    write_file(&puzzle_path, &puzzle)?;
+
+    println!();
+    println!("{}", html_to_text(&puzzle));
+    Ok(())
+}
+
+/// Submits an answer for one part of a day.
+pub fn submit(day: Day, part: u8, result: &str) -> Result<SubmissionOutcome, AocClientError> {
+    AocClient::from_env()?.submit(day, part, result)
+}
+
+/// Writes a file, creating the year's data directories if they do not exist yet.
+fn write_file(path: &Path, content: &str) -> Result<(), io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)
+}
+
+fn get_input_path(day: Day) -> PathBuf {
+    Year::current()
+        .data_dir()
+        .join("inputs")
+        .join(format!("{day}.txt"))
+}
+
+pub(crate) fn get_puzzle_path(day: Day) -> PathBuf {
+    Year::current()
+        .data_dir()
+        .join("puzzles")
+        .join(format!("{day}.md"))
+}
+
+/// Looks up the session cookie in `AOC_SESSION`, `~/.adventofcode.session` and `<config_dir>/adventofcode.session`.
+fn find_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION")
+        && !session.trim().is_empty()
+    {
+        return Some(session.trim().to_string());
+    }
+
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from);
+
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".config")));
+
+    [
+        home.map(|home| home.join(SESSION_FILE_NAME)),
+        config_dir.map(|dir| dir.join("adventofcode.session")),
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(|path| read_session_file(&path))
+}
+
+fn read_session_file(path: &Path) -> Option<String> {
+    let session = fs::read_to_string(path).ok()?;
+    let session = session.trim();
+    (!session.is_empty()).then(|| session.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns the inner html of every `<tag>` element in a document. Does not handle nesting of the same tag.
+fn extract_elements<'a>(html: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{tag}");
+    let close = format!("</{tag}>");
+
+    let mut elements = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find(&open) {
+        let after_open = &rest[start..];
+        let Some(content_start) = after_open.find('>') else {
+            break;
+        };
+        let content = &after_open[content_start + 1..];
+        let Some(end) = content.find(&close) else {
+            break;
+        };
+        elements.push(&content[..end]);
+        rest = &content[end + close.len()..];
+    }
+
+    elements
+}
+
+/// Returns the title of a stored puzzle description, e.g. `Day 1: Secret Entrance`.
+pub fn puzzle_title(puzzle: &str) -> Option<String> {
+    let heading = extract_elements(puzzle, "h2").into_iter().next()?;
+    let title = html_to_text(heading);
+    let title = title.trim().trim_matches('-').trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Example input and expected answer of one part of a puzzle description, as far as they can be detected.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PuzzlePart {
+    /// The first code block of the part.
+    pub example: Option<String>,
+    /// The last emphasized code of the part, which usually is the answer for the example.
+    pub answer: Option<String>,
+}
+
+/// Detects the example and the expected answer of every part of a stored puzzle description.
+/// Parts start with a `<h2>` heading.
+pub fn puzzle_parts(puzzle: &str) -> Vec<PuzzlePart> {
+    let starts: Vec<usize> = puzzle.match_indices("<h2").map(|(i, _)| i).collect();
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, start)| {
+            let part = &puzzle[*start..starts.get(i + 1).copied().unwrap_or(puzzle.len())];
+
+            let answer = [
+                ("<code><em>", "</em></code>"),
+                ("<em><code>", "</code></em>"),
+            ]
+            .into_iter()
+            .flat_map(|(open, close)| {
+                part.match_indices(open).filter_map(move |(at, _)| {
+                    let content = &part[at + open.len()..];
+                    content.find(close).map(|end| (at, &content[..end]))
+                })
+            })
+            .max_by_key(|(at, _)| *at)
+            .map(|(_, answer)| html_to_text(answer));
+
+            PuzzlePart {
+                example: extract_elements(part, "pre")
+                    .first()
+                    .map(|pre| code_to_text(pre)),
+                answer,
+            }
+        })
+        .collect()
+}
+
+/// Converts the content of a code block to plain text. Unlike [`html_to_text`], whitespace is kept as is.
+fn code_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        match rest[start..].find('>') {
+            Some(end) => rest = &rest[start + end + 1..],
+            None => {
+                rest = &rest[start..];
+                break;
+            }
+        }
+    }
+    text.push_str(rest);
+
+    decode_entities(&text)
+}
+
+/// Converts a html fragment to plain text, keeping line breaks of block elements.
+pub fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        match name {
+            "p" | "pre" | "h2" | "ul" | "article" if tag.starts_with('/') => text.push('\n'),
+            "li" if !tag.starts_with('/') => text.push_str("  - "),
+            "li" | "br" => text.push('\n'),
+            _ => {}
+        }
+
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+
+    decode_entities(&text)
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    use super::{
+        AocClient, Hint, PuzzlePart, SubmissionOutcome, html_to_text, puzzle_parts, puzzle_title,
+    };
+    use crate::day;
+    use crate::template::Year;
+
+    /// Serves a single request with `body` and returns the raw request it received.
+    fn mock_server(body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.push_str(&String::from_utf8(request_body).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (url, server) = mock_server("1\n2\n3\n");
+        let client = AocClient::new("abc", Year::new(2025).unwrap()).with_base_url(&url);
+
+        assert_eq!(client.fetch_input(day!(5)).unwrap(), "1\n2\n3\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2025/day/5/input HTTP/1.1"));
+        assert!(request.contains("session=abc"));
+    }
+
+    #[test]
+    fn fetches_puzzle_articles() {
+        let (url, server) = mock_server(
+            "<html><main><article class=\"day-desc\"><h2>--- Day 1 ---</h2><p>Hi</p></article><p>Footer</p></main></html>",
+        );
+        let client = AocClient::new("abc", Year::new(2025).unwrap()).with_base_url(&url);
+
+        let puzzle = client.fetch_puzzle(day!(1)).unwrap();
+        assert_eq!(puzzle, "<h2>--- Day 1 ---</h2><p>Hi</p>");
+        assert!(
+            server
+                .join()
+                .unwrap()
+                .starts_with("GET /2025/day/1 HTTP/1.1")
+        );
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, server) = mock_server(
+            "<main><article><p>That's not the right answer; your answer is too high.</p></article></main>",
+        );
+        let client = AocClient::new("abc", Year::new(2024).unwrap()).with_base_url(&url);
+
+        let outcome = client.submit(day!(12), 2, "42").unwrap();
+        assert_eq!(outcome, SubmissionOutcome::Incorrect(Some(Hint::TooHigh)));
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/12/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn classifies_submission_messages() {
+        assert_eq!(
+            SubmissionOutcome::from_message(
+                "That's the right answer! You are one gold star closer."
+            ),
+            SubmissionOutcome::Correct
+        );
+        assert_eq!(
+            SubmissionOutcome::from_message("That's not the right answer; your answer is too low."),
+            SubmissionOutcome::Incorrect(Some(Hint::TooLow))
+        );
+        assert_eq!(
+            SubmissionOutcome::from_message(
+                "You gave an answer too recently. You have 42s left to wait."
+            ),
+            SubmissionOutcome::TooRecent(Some("42s".into()))
+        );
+        assert_eq!(
+            SubmissionOutcome::from_message("You don't seem to be solving the right level."),
+            SubmissionOutcome::WrongLevel
+        );
+    }
+
+    #[test]
+    fn converts_html_to_text() {
+        let text = html_to_text(
+            "<h2>--- Day 1 ---</h2><p>A &lt;b&gt; <em>c</em></p><ul><li>x</li><li>y</li></ul>",
+        );
+        assert_eq!(text, "--- Day 1 ---\nA <b> c\n  - x\n  - y");
+    }
+
+    #[test]
+    fn extracts_puzzle_titles() {
+        let puzzle = "<article class=\"day-desc\"><h2>--- Day 1: Secret Entrance ---</h2><p>...</p></article>";
+        assert_eq!(
+            puzzle_title(puzzle).as_deref(),
+            Some("Day 1: Secret Entrance")
+        );
+        assert_eq!(puzzle_title("<p>no title</p>"), None);
+    }
+
+    #[test]
+    fn detects_examples_and_answers() {
+        let puzzle = "<h2>--- Day 1: Secret Entrance ---</h2>\
+            <p>For example:</p><pre><code>  L68\n&lt;L30\n</code></pre>\
+            <p>The dial points at <code>0</code> a total of <code><em>3</em></code> times.</p>\
+            \n\n<h2 id=\"part2\">--- Part Two ---</h2>\
+            <p>Following the same rotations, it points at zero <em><code>6</code></em> times.</p>";
+
+        assert_eq!(
+            puzzle_parts(puzzle),
+            vec![
+                PuzzlePart {
+                    example: Some("  L68\n<L30\n".into()),
+                    answer: Some("3".into()),
+                },
+                PuzzlePart {
+                    example: None,
+                    answer: Some("6".into()),
+                },
+            ]
+        );
+    }
+}
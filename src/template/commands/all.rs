@@ -1,5 +1,17 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::commands::verify;
+use crate::template::runner::Limits;
+use crate::template::{Error, all_days, run_multi::run_multi};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(
+    is_release: bool,
+    is_verify: bool,
+    jobs: usize,
+    limits: &Limits,
+) -> Result<(), Error> {
+    if is_verify {
+        verify::handle(None, is_release, limits, false)
+    } else {
+        run_multi(&all_days().collect(), is_release, None, jobs, limits);
+        Ok(())
+    }
 }
@@ -1,17 +1,7 @@
-use std::process;
+use crate::template::examples::update_from_puzzle;
+use crate::template::{Day, Error, aoc_client};
 
-use crate::template::{Day, aoc_cli};
-
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+pub fn handle(day: Day) -> Result<(), Error> {
+    aoc_client::read(day).map_err(|source| Error::Client { day, source })?;
+    update_from_puzzle(day)
 }
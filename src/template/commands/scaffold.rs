@@ -1,13 +1,67 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
+    env,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
 };
 
-use crate::template::Day;
+use crate::template::aoc_client::{get_puzzle_path, puzzle_title};
+use crate::template::examples::Examples;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::{Day, Error, Year};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Name of the template that `scaffold` uses without `--template`.
+pub const DEFAULT_TEMPLATE: &str = "plain";
+
+/// Built-in module templates, selected by name with `--template <name>`.
+const TEMPLATES: [(&str, &str); 5] = [
+    (
+        "plain",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/plain.txt"
+        )),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+    ),
+    (
+        "graph",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/graph.txt"
+        )),
+    ),
+    (
+        "nom",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/nom.txt"
+        )),
+    ),
+    (
+        "shared",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/shared.txt"
+        )),
+    ),
+];
+
+/// Directory of user-defined templates. `templates/<name>.txt` takes precedence over a built-in template of the same name.
+const USER_TEMPLATES_DIR: &str = "templates";
+
+/// Example manifest of a new day. The answers are filled in from the puzzle description.
+const EXAMPLES_TEMPLATE: &str = r#"{
+  "examples": [
+    { "file": "%DAY%.txt", "part_1": null, "part_2": null }
+  ]
+}
+"#;
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -19,61 +73,180 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Creates an empty file unless it exists already, e.g. because the input was downloaded first.
+/// Returns whether the file was created.
+fn create_missing_file(path: &Path) -> Result<bool, std::io::Error> {
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Reads a module template by name. User-defined templates take precedence over built-in ones.
+fn read_template(name: &str) -> Result<String, Error> {
+    let path = Path::new(USER_TEMPLATES_DIR).join(format!("{name}.txt"));
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
+    match fs::read_to_string(&path) {
+        Ok(template) => return Ok(template),
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => return Err(Error::io(path)(e)),
+    }
+
+    TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, template)| (*template).to_string())
+        .ok_or_else(|| Error::UnknownTemplate {
+            name: name.to_string(),
+            available: available_templates(),
+        })
+}
+
+/// Names of the built-in and user-defined templates, sorted and without duplicates.
+fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = TEMPLATES.iter().map(|(name, _)| name.to_string()).collect();
+
+    if let Ok(entries) = fs::read_dir(USER_TEMPLATES_DIR) {
+        names.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| PathBuf::from(entry.file_name()))
+                .filter(|path| path.extension().is_some_and(|x| x == "txt"))
+                .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string())),
+        );
+    }
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Values of the placeholders in a template:
+/// `%YEAR%`, `%DAY%` (zero-padded), `%DAY_NUMBER%`, `%TITLE%` and the answers of the first example, `%EXAMPLE_PART_1%` and `%EXAMPLE_PART_2%`.
+/// The example answers are read from the manifest, which `download` fills in, and are `?` if no answer is known.
+fn placeholders(year: Year, day: Day) -> Vec<(&'static str, String)> {
+    let title = fs::read_to_string(get_puzzle_path(day))
+        .ok()
+        .and_then(|puzzle| puzzle_title(&puzzle))
+        .unwrap_or_else(|| format!("Day {}", day.into_inner()));
+
+    let examples = Examples::read(year, day).ok().flatten().unwrap_or_default();
+    let example_answer = |part: usize| {
+        examples
+            .data
+            .iter()
+            .find_map(|example| example.answers[part].answer().map(str::to_string))
+            .unwrap_or_else(|| "?".into())
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
+    vec![
+        ("%YEAR%", year.to_string()),
+        ("%DAY%", day.to_string()),
+        ("%DAY_NUMBER%", day.into_inner().to_string()),
+        ("%TITLE%", title),
+        ("%EXAMPLE_PART_1%", example_answer(0)),
+        ("%EXAMPLE_PART_2%", example_answer(1)),
+    ]
+}
+
+fn render(template: &str, placeholders: &[(&str, String)]) -> String {
+    placeholders
+        .iter()
+        .fold(template.to_string(), |rendered, (placeholder, value)| {
+            rendered.replace(placeholder, value)
+        })
+}
+
+pub fn handle(day: Day, overwrite: bool, template: &str) -> Result<(), Error> {
+    let data_dir = Year::current().data_dir();
+    let input_path = data_dir.join("inputs").join(format!("{day}.txt"));
+    let example_path = data_dir.join("examples").join(format!("{day}.txt"));
+    let manifest_path = Examples::file_path(Year::current(), day);
+    let module_path = get_path_for_bin(day);
+    let module = render(
+        &read_template(template)?,
+        &placeholders(Year::current(), day),
+    );
+
+    for dir in [&input_path, &example_path]
+        .into_iter()
+        .filter_map(|p| p.parent())
+    {
+        fs::create_dir_all(dir).map_err(Error::io(dir))?;
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    let mut file = safe_create_file(&module_path, overwrite).map_err(|e| {
+        if e.kind() == ErrorKind::AlreadyExists {
+            Error::AlreadyExists(module_path.clone().into())
+        } else {
+            Error::io(&module_path)(e)
         }
+    })?;
+
+    file.write_all(module.as_bytes())
+        .map_err(Error::io(&module_path))?;
+    println!("Created module file \"{}\"", &module_path);
+
+    if create_missing_file(&input_path).map_err(Error::io(&input_path))? {
+        println!("Created empty input file \"{}\"", input_path.display());
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    if create_missing_file(&example_path).map_err(Error::io(&example_path))? {
+        println!("Created empty example file \"{}\"", example_path.display());
+    }
+
+    if !manifest_path.exists() {
+        fs::write(
+            &manifest_path,
+            EXAMPLES_TEMPLATE.replace("%DAY%", &day.to_string()),
+        )
+        .map_err(Error::io(&manifest_path))?;
+        println!("Created example manifest \"{}\"", manifest_path.display());
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    let year = Year::current();
+    if env::var("AOC_YEAR").is_ok_and(|x| x == year.to_string()) {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    } else {
+        println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{TEMPLATES, render};
+
+    #[test]
+    fn renders_placeholders() {
+        let rendered = render(
+            "//! %YEAR%, %TITLE%\nadvent_of_code::solution!(%DAY_NUMBER%);\n// %DAY%: %EXAMPLE_PART_1%",
+            &[
+                ("%YEAR%", "2025".into()),
+                ("%DAY%", "07".into()),
+                ("%DAY_NUMBER%", "7".into()),
+                ("%TITLE%", "Day 7: Laboratories".into()),
+                ("%EXAMPLE_PART_1%", "21".into()),
+            ],
+        );
+        assert_eq!(
+            rendered,
+            "//! 2025, Day 7: Laboratories\nadvent_of_code::solution!(7);\n// 07: 21"
+        );
+    }
+
+    #[test]
+    fn builtin_templates_use_the_solution_macro() {
+        for (name, template) in TEMPLATES {
+            assert!(
+                template.contains("advent_of_code::solution!(%DAY_NUMBER%"),
+                "template {name}"
+            );
+        }
+    }
 }
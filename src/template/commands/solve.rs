@@ -1,9 +1,107 @@
-use std::process::{Command, Stdio};
+use std::{
+    io::{BufRead, BufReader},
+    process::{Command, Stdio},
+};
 
-use crate::template::Day;
+use crate::template::run_multi::{child_commands, get_bin_name};
+use crate::template::runner::{InputSource, Limits, OutputFormat, PartReport};
+use crate::template::{Day, Error};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    format: Option<OutputFormat>,
+    input: &InputSource,
+    limits: &Limits,
+) -> Result<(), Error> {
+    let cmd_args = command_args(day, release, dhat, submit_part, format, input, limits);
+
+    let command = format!("cargo {}", cmd_args.join(" "));
+    let to_error = |source| Error::Command {
+        command: command.clone(),
+        source,
+    };
+
+    let status = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(to_error)?
+        .wait()
+        .map_err(to_error)?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::CommandFailed {
+            command,
+            status: status.code(),
+        })
+    }
+}
+
+/// Like [`handle`], but collects the machine-readable part reports of the solution instead of printing them.
+/// Other output of the solution is forwarded. Always builds the solution from source, unlike the runs of `all`.
+pub fn collect(
+    day: Day,
+    release: bool,
+    input: &InputSource,
+    limits: &Limits,
+) -> Result<Vec<PartReport>, Error> {
+    let mut cmd_args = command_args(
+        day,
+        release,
+        false,
+        None,
+        Some(OutputFormat::Json),
+        input,
+        limits,
+    );
+    cmd_args.insert(1, "--quiet".to_string());
+
+    let command = format!("cargo {}", cmd_args.join(" "));
+    let to_error = |source| Error::Command {
+        command: command.clone(),
+        source,
+    };
+
+    let mut child = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(to_error)?;
+
+    let reports = child.stdout.take().map_or_else(Vec::new, |stdout| {
+        child_commands::parse_reports(BufReader::new(stdout).lines().map_while(Result::ok))
+    });
+
+    let status = child.wait().map_err(to_error)?;
+
+    // parts that panic or time out still report, only fail if the solution did not run at all.
+    if status.success() || !reports.is_empty() {
+        Ok(reports)
+    } else {
+        Err(Error::CommandFailed {
+            command,
+            status: status.code(),
+        })
+    }
+}
+
+fn command_args(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    format: Option<OutputFormat>,
+    input: &InputSource,
+    limits: &Limits,
+) -> Vec<String> {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), get_bin_name(day)];
 
     if dhat {
         cmd_args.extend([
@@ -23,12 +121,13 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+    cmd_args.extend(input.to_args());
+
+    if let Some(format) = format {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
 
-    cmd.wait().unwrap();
+    cmd_args.extend(limits.to_args());
+    cmd_args
 }
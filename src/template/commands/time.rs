@@ -1,16 +1,35 @@
 use std::collections::HashSet;
 
+use crate::template::compare::{compare, print_comparisons};
+use crate::template::history::{self, RunInfo};
 use crate::template::run_multi::run_multi;
+use crate::template::runner::{BenchConfig, Limits};
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{Day, Error, all_days, readme_benchmarks};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+/// Options of `time --compare`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompareOptions {
+    /// Exit with a non-zero status if any part regressed by more than this percentage.
+    pub fail_threshold: Option<f64>,
+}
+
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    bench: &BenchConfig,
+    compare_options: Option<CompareOptions>,
+) -> Result<(), Error> {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if compare_options.is_some() {
+                // when comparing, bench all days that have a baseline.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
@@ -21,11 +40,33 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, Some(bench), 1, &Limits::default()).unwrap();
+
+    let mut exceeded_threshold = None;
+
+    if let Some(compare_options) = compare_options {
+        let comparisons = compare(&stored_timings, &timings);
+        print_comparisons(&comparisons);
+
+        if let Some(threshold) = compare_options.fail_threshold
+            && comparisons
+                .iter()
+                .flat_map(|c| c.parts.iter().flatten())
+                .any(|c| c.exceeds(threshold))
+        {
+            exceeded_threshold = Some(threshold);
+        }
+    }
 
     if store {
+        if let Err(e) = history::append(&timings, &RunInfo::current()) {
+            eprintln!("Failed to append to timings history: {e}");
+        }
+
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings
+            .store_file()
+            .map_err(Error::io(Timings::file_path()))?;
 
         println!();
         match readme_benchmarks::update(merged_timings) {
@@ -37,4 +78,14 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    match exceeded_threshold {
+        Some(threshold) => Err(Error::Regression { threshold }),
+        None => Ok(()),
+    }
+}
+
+/// Prints how the stored timings of a day evolved over time.
+pub fn history(day: Day) {
+    history::print_history(day, &history::read_day(day));
 }
@@ -0,0 +1,29 @@
+use std::collections::HashSet;
+
+use crate::template::runner::Limits;
+use crate::template::verify::{print_matrix, verify};
+use crate::template::{Day, Error, all_days};
+
+pub fn handle(
+    day: Option<Day>,
+    is_release: bool,
+    limits: &Limits,
+    all_inputs: bool,
+) -> Result<(), Error> {
+    let days_to_verify = day.map_or_else(|| all_days().collect(), |day| HashSet::from([day]));
+
+    let reports = verify(&days_to_verify, is_release, limits, all_inputs)?;
+    print_matrix(&reports);
+
+    let failures = reports
+        .iter()
+        .flat_map(|report| report.parts.iter())
+        .filter(|status| status.is_failure())
+        .count();
+
+    if failures > 0 {
+        return Err(Error::VerificationFailed { failures });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::commands::solve;
+use crate::template::examples::{ExampleInput, Examples};
+use crate::template::run_multi::{get_bin_name, get_path_for_bin};
+use crate::template::runner::{
+    InputSource, Limits, PARSE_PART, PartReport, part_label, print_error, print_result,
+};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, Error, Year};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification times of the watched files. Files that do not exist map to `None`.
+type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+/// Re-runs the example tests and the solution of a day whenever its source, examples or input change.
+/// Runs until it is interrupted.
+pub fn handle(day: Day, release: bool, limits: &Limits) -> Result<(), Error> {
+    let year = Year::current();
+    let mut watched = snapshot(year, day);
+    let mut previous: Option<Vec<PartReport>> = None;
+
+    loop {
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        if run_tests(day, release)? {
+            println!("Tests: ✔ pass");
+        } else {
+            println!("Tests: ✖ fail");
+        }
+
+        match solve::collect(day, release, &InputSource::default(), limits) {
+            Ok(reports) => {
+                print_diff(previous.as_deref(), &reports);
+                previous = Some(reports);
+            }
+            Err(e) => eprintln!("Error: {e}"),
+        }
+
+        println!();
+        println!("{ANSI_ITALIC}Watching for changes, press Ctrl-C to stop.{ANSI_RESET}");
+
+        let changed = loop {
+            thread::sleep(POLL_INTERVAL);
+            let next = snapshot(year, day);
+            let changed: Vec<PathBuf> = next
+                .iter()
+                .filter(|(path, modified)| watched.get(*path) != Some(*modified))
+                .map(|(path, _)| path.clone())
+                .collect();
+            watched = next;
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+
+        println!();
+        for path in changed {
+            println!("Changed: {}", path.display());
+        }
+    }
+}
+
+/// Collects the modification times of the files that a day depends on:
+/// its module, its puzzle input, its example manifest and the example files.
+fn snapshot(year: Year, day: Day) -> Snapshot {
+    let examples_dir = year.data_dir().join("examples");
+
+    let mut paths = vec![
+        PathBuf::from(get_path_for_bin(day)),
+        year.data_dir().join("inputs").join(format!("{day}.txt")),
+        Examples::file_path(year, day),
+    ];
+
+    // examples without a manifest follow the `{day}.txt` and `{day}-{n}.txt` naming scheme.
+    if let Ok(entries) = fs::read_dir(&examples_dir) {
+        paths.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| {
+                            name == format!("{day}.txt") || name.starts_with(&format!("{day}-"))
+                        })
+                }),
+        );
+    }
+
+    if let Ok(Some(examples)) = Examples::read(year, day) {
+        paths.extend(
+            examples
+                .data
+                .iter()
+                .filter_map(|example| match &example.input {
+                    ExampleInput::File(file) => Some(examples_dir.join(file)),
+                    ExampleInput::Inline(_) => None,
+                }),
+        );
+    }
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Runs the tests of the day's bin, which check the examples. Returns whether they passed.
+fn run_tests(day: Day, release: bool) -> Result<bool, Error> {
+    let mut args = vec![
+        "test".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        get_bin_name(day),
+    ];
+
+    if release {
+        args.push("--release".to_string());
+    }
+
+    let command = format!("cargo {}", args.join(" "));
+
+    let status = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .map_err(|source| Error::Command { command, source })?;
+
+    Ok(status.success())
+}
+
+/// Prints the results of a run and how they differ from the `previous` run.
+fn print_diff(previous: Option<&[PartReport]>, reports: &[PartReport]) {
+    if reports.is_empty() {
+        println!("Not solved.");
+        return;
+    }
+
+    for report in reports {
+        let part = part_label(report.part);
+
+        if let Some(error) = &report.error {
+            print_error(&part, error);
+            continue;
+        }
+
+        let before = previous
+            .and_then(|previous| previous.iter().find(|x| x.part == report.part))
+            .filter(|before| before.error.is_none());
+
+        // watched runs are never benched, so there is a single sample per part.
+        let duration = report.duration();
+        let details = match before {
+            None => format!(" ({duration:.1?})"),
+            Some(before) if before.result == report.result => {
+                format!(" ({duration:.1?}, was {:.1?})", before.duration())
+            }
+            Some(before) => format!(
+                " ({duration:.1?}, changed from {})",
+                before.result.as_deref().unwrap_or("nothing")
+            ),
+        };
+
+        if report.part == PARSE_PART {
+            println!("{part}:{details}");
+        } else {
+            print_result(&report.result, &part, &details);
+        }
+    }
+}
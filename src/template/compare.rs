@@ -0,0 +1,222 @@
+/// Compares benchmark results against stored timings.
+use std::{fmt::Display, time::Duration};
+
+use crate::template::runner::BenchStats;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+
+/// Changes smaller than this percentage are considered noise, regardless of their significance.
+const NOISE_THRESHOLD: f64 = 2.0;
+
+/// z-score of a two-sided test at 95% confidence.
+const Z_CRITICAL: f64 = 1.96;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Improved,
+    Regressed,
+    Unchanged,
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::Improved => write!(f, "▼ improved"),
+            Change::Regressed => write!(f, "▲ regressed"),
+            Change::Unchanged => write!(f, "no change"),
+        }
+    }
+}
+
+/// Comparison of a part's benchmark with its stored baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    pub baseline: f64,
+    pub current: f64,
+    /// Relative change of the mean in percent. Positive values mean the part got slower.
+    pub percent: f64,
+    pub change: Change,
+}
+
+impl Comparison {
+    /// Compares two benchmarks with Welch's z-test on their means.
+    /// A change is flagged if it is statistically significant and larger than the noise threshold.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new(baseline: &BenchStats, current: &BenchStats) -> Self {
+        let delta = current.mean - baseline.mean;
+        let percent = if baseline.mean > 0.0 {
+            delta / baseline.mean * 100.0
+        } else {
+            0.0
+        };
+
+        let variance = |stats: &BenchStats| {
+            let n = stats.samples.saturating_sub(stats.outliers).max(1) as f64;
+            stats.std_dev.powi(2) / n
+        };
+        let standard_error = (variance(baseline) + variance(current)).sqrt();
+
+        let is_significant = if standard_error > 0.0 {
+            delta.abs() / standard_error > Z_CRITICAL
+        } else {
+            delta != 0.0
+        };
+
+        let change = if !is_significant || percent.abs() < NOISE_THRESHOLD {
+            Change::Unchanged
+        } else if delta > 0.0 {
+            Change::Regressed
+        } else {
+            Change::Improved
+        };
+
+        Self {
+            baseline: baseline.mean,
+            current: current.mean,
+            percent,
+            change,
+        }
+    }
+
+    /// Whether this part regressed by more than `threshold` percent.
+    pub fn exceeds(&self, threshold: f64) -> bool {
+        self.change == Change::Regressed && self.percent > threshold
+    }
+}
+
+/// Comparisons of both parts of a day. A part is `None` if there is no baseline or no current benchmark.
+#[derive(Debug, Clone)]
+pub struct DayComparison {
+    pub day: Day,
+    pub parts: [Option<Comparison>; 2],
+}
+
+pub fn compare(stored: &Timings, current: &Timings) -> Vec<DayComparison> {
+    current
+        .data
+        .iter()
+        .map(|timing| {
+            let baseline = stored.data.iter().find(|t| t.day == timing.day);
+
+            let part = |stats: fn(&Timing) -> Option<BenchStats>| {
+                let baseline = stats(baseline?)?;
+                let current = stats(timing)?;
+                Some(Comparison::new(&baseline, &current))
+            };
+
+            DayComparison {
+                day: timing.day,
+                parts: [
+                    part(|t| t.part_1.as_ref()?.stats),
+                    part(|t| t.part_2.as_ref()?.stats),
+                ],
+            }
+        })
+        .collect()
+}
+
+pub fn print_comparisons(comparisons: &[DayComparison]) {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let fmt = |nanos: f64| format!("{:.1?}", Duration::from_nanos(nanos as u64));
+
+    println!("\n{ANSI_BOLD}Comparison with stored timings{ANSI_RESET}");
+
+    for comparison in comparisons {
+        println!("\n{ANSI_BOLD}Day {}{ANSI_RESET}", comparison.day);
+
+        for (index, part) in comparison.parts.iter().enumerate() {
+            match part {
+                Some(c) => println!(
+                    "Part {}: {} → {} ({:+.1}%) {}",
+                    index + 1,
+                    fmt(c.baseline),
+                    fmt(c.current),
+                    c.percent,
+                    c.change
+                ),
+                None => println!("Part {}: {ANSI_ITALIC}no baseline{ANSI_RESET}", index + 1),
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Change, Comparison, compare};
+    use crate::day;
+    use crate::template::runner::BenchStats;
+    use crate::template::timings::{PartTiming, Timing, Timings};
+
+    fn stats(mean: f64, std_dev: f64) -> BenchStats {
+        BenchStats {
+            mean,
+            median: mean,
+            min: mean - std_dev,
+            max: mean + std_dev,
+            std_dev,
+            p95: mean + std_dev,
+            samples: 1000,
+            outliers: 0,
+        }
+    }
+
+    fn timing(part_1_stats: Option<BenchStats>) -> Timing {
+        Timing {
+            day: day!(1),
+            parse: None,
+            part_1: part_1_stats.map(|stats| PartTiming::new(stats.mean, 1000, Some(stats))),
+            part_2: None,
+            total_nanos: 0.0,
+        }
+    }
+
+    #[test]
+    fn flags_regressions() {
+        let comparison = Comparison::new(&stats(100.0, 5.0), &stats(120.0, 5.0));
+        assert_eq!(comparison.change, Change::Regressed);
+        assert!((comparison.percent - 20.0).abs() < 1e-9);
+        assert!(comparison.exceeds(10.0));
+        assert!(!comparison.exceeds(25.0));
+    }
+
+    #[test]
+    fn flags_improvements() {
+        let comparison = Comparison::new(&stats(100.0, 5.0), &stats(80.0, 5.0));
+        assert_eq!(comparison.change, Change::Improved);
+        assert!(!comparison.exceeds(0.0));
+    }
+
+    #[test]
+    fn ignores_noise() {
+        // significant, but below the noise threshold.
+        let comparison = Comparison::new(&stats(100.0, 0.1), &stats(101.0, 0.1));
+        assert_eq!(comparison.change, Change::Unchanged);
+
+        // large, but not significant.
+        let comparison = Comparison::new(&stats(100.0, 1000.0), &stats(150.0, 1000.0));
+        assert_eq!(comparison.change, Change::Unchanged);
+    }
+
+    #[test]
+    fn compares_timings() {
+        let stored = Timings {
+            data: vec![timing(Some(stats(100.0, 1.0)))],
+        };
+        let current = Timings {
+            data: vec![timing(Some(stats(200.0, 1.0)))],
+        };
+
+        let comparisons = compare(&stored, &current);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(
+            comparisons[0].parts[0].map(|c| c.change),
+            Some(Change::Regressed)
+        );
+        assert!(comparisons[0].parts[1].is_none());
+
+        let comparisons = compare(&Timings::default(), &current);
+        assert!(comparisons[0].parts[0].is_none());
+    }
+}
@@ -0,0 +1,185 @@
+use std::{fmt::Display, io, path::PathBuf};
+
+use crate::template::aoc_client::AocClientError;
+use crate::template::{Day, Year};
+
+/// Errors of the template commands.
+///
+/// # Exit codes
+/// The runner exits with [`Error::exit_code`]:
+///  1. a check failed, i.e. `verify` found a wrong answer or `time --compare` a regression.
+///  2. invalid command-line arguments.
+///  3. a file could not be read or written.
+///  4. a request to the advent of code website failed.
+///  5. `cargo` could not be run.
+///  6. the input of a solution is missing or empty.
+///  7. a part of a solution exceeded its `--timeout`.
+///
+/// When a solution exits with a non-zero status, its status is passed through.
+#[derive(Debug)]
+pub enum Error {
+    /// A file could not be read or written.
+    Io { path: PathBuf, source: io::Error },
+    /// The input of a day is missing or empty.
+    MissingInput {
+        year: Year,
+        day: Day,
+        path: PathBuf,
+        is_empty: bool,
+    },
+    /// A file that should be created already exists.
+    AlreadyExists(PathBuf),
+    /// No built-in or user-defined scaffold template has this name.
+    UnknownTemplate {
+        name: String,
+        available: Vec<String>,
+    },
+    /// A request to the advent of code website failed.
+    Client { day: Day, source: AocClientError },
+    /// A child command could not be spawned or waited on.
+    Command { command: String, source: io::Error },
+    /// A child command exited with a non-zero status. `status` is `None` if it was terminated by a signal.
+    CommandFailed {
+        command: String,
+        status: Option<i32>,
+    },
+    /// Solutions returned results that differ from the stored answers.
+    VerificationFailed { failures: usize },
+    /// At least one part regressed by more than `threshold` percent.
+    Regression { threshold: f64 },
+}
+
+/// Exit code of [`Error::MissingInput`].
+pub(crate) const MISSING_INPUT_EXIT_CODE: i32 = 6;
+
+/// Exit code of a solution bin if a part exceeds its `--timeout`.
+pub(crate) const TIMEOUT_EXIT_CODE: i32 = 7;
+
+impl Error {
+    pub(crate) fn io(path: impl Into<PathBuf>) -> impl FnOnce(io::Error) -> Self {
+        let path = path.into();
+        move |source| Error::Io { path, source }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::VerificationFailed { .. } | Error::Regression { .. } => 1,
+            Error::UnknownTemplate { .. } => 2,
+            Error::Io { .. } | Error::AlreadyExists(_) => 3,
+            Error::Client { .. } => 4,
+            Error::Command { .. } => 5,
+            Error::MissingInput { .. } => MISSING_INPUT_EXIT_CODE,
+            Error::CommandFailed { status, .. } => status.unwrap_or(1),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "\"{}\": {source}", path.display()),
+            Error::MissingInput {
+                year,
+                day,
+                path,
+                is_empty,
+            } => write!(
+                f,
+                "the input file \"{}\" is {}. Run `cargo download {day} --year {year}` to fetch it, or pass --download to `cargo solve`.",
+                path.display(),
+                if *is_empty { "empty" } else { "missing" }
+            ),
+            Error::AlreadyExists(path) => write!(
+                f,
+                "\"{}\" already exists. Pass --overwrite to replace it.",
+                path.display()
+            ),
+            Error::UnknownTemplate { name, available } => write!(
+                f,
+                "there is no template \"{name}\". Available templates: {}.",
+                available.join(", ")
+            ),
+            Error::Client { day, source } => write!(f, "request for day {day} failed: {source}"),
+            Error::Command { command, source } => {
+                write!(f, "failed to run `{command}`: {source}")
+            }
+            Error::CommandFailed {
+                command,
+                status: Some(status),
+            } => write!(f, "`{command}` exited with status {status}."),
+            Error::CommandFailed {
+                command,
+                status: None,
+            } => write!(f, "`{command}` was terminated."),
+            Error::VerificationFailed { failures } => {
+                write!(f, "{failures} part(s) did not match the stored answers.")
+            }
+            Error::Regression { threshold } => {
+                write!(f, "at least one part regressed by more than {threshold}%.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } | Error::Command { source, .. } => Some(source),
+            Error::Client { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Error;
+    use std::io;
+
+    #[test]
+    fn maps_errors_to_exit_codes() {
+        let not_found = || io::Error::from(io::ErrorKind::NotFound);
+
+        assert_eq!(Error::VerificationFailed { failures: 2 }.exit_code(), 1);
+        assert_eq!(
+            Error::UnknownTemplate {
+                name: "tree".into(),
+                available: vec!["plain".into()]
+            }
+            .exit_code(),
+            2
+        );
+        assert_eq!(
+            Error::io("data/2025/inputs/01.txt")(not_found()).exit_code(),
+            3
+        );
+        assert_eq!(
+            Error::Command {
+                command: "cargo run".into(),
+                source: not_found()
+            }
+            .exit_code(),
+            5
+        );
+        assert_eq!(
+            Error::CommandFailed {
+                command: "cargo run".into(),
+                status: Some(101)
+            }
+            .exit_code(),
+            101
+        );
+    }
+
+    #[test]
+    fn displays_context() {
+        let error = Error::io("data/2025/inputs/01.txt")(io::Error::from(io::ErrorKind::NotFound));
+        assert!(
+            error
+                .to_string()
+                .starts_with("\"data/2025/inputs/01.txt\": ")
+        );
+    }
+}
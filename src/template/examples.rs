@@ -0,0 +1,590 @@
+/// Example inputs from the puzzle descriptions and their expected answers.
+/// Every day can declare its examples in a manifest, `data/{year}/examples/{day}.json`:
+///
+/// ```json
+/// {
+///   "examples": [
+///     { "file": "11-1.txt", "part_1": 5 },
+///     { "name": "second", "file": "11-2.txt", "part_2": 2, "params": { "iterations": 10 } }
+///   ]
+/// }
+/// ```
+///
+/// The test that the [`solution!`](crate::solution) macro generates runs each part on all examples with an answer for it.
+use std::{collections::HashMap, fmt::Display, fs, io, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::{PuzzlePart, get_puzzle_path, puzzle_parts};
+use crate::template::runner::PartReport;
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, Error, Params, Year, migrate_legacy_data};
+
+/// Input of an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExampleInput {
+    /// A file in the examples directory of the year.
+    File(String),
+    /// An input that is inlined in the manifest.
+    Inline(String),
+}
+
+/// Expected answer of a part for an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expected {
+    /// The part is not checked. Written as `null` or left out in the manifest.
+    Unchecked,
+    /// The part returns `None`. Written as `false` in the manifest.
+    Nothing,
+    Answer(String),
+}
+
+impl Expected {
+    pub fn answer(&self) -> Option<&str> {
+        match self {
+            Expected::Answer(answer) => Some(answer),
+            Expected::Unchecked | Expected::Nothing => None,
+        }
+    }
+
+    /// Whether the result of a part matches. Parts that fail never match.
+    fn matches(&self, report: &PartReport) -> bool {
+        match self {
+            Expected::Unchecked => true,
+            Expected::Nothing => report.error.is_none() && report.result.is_none(),
+            Expected::Answer(answer) => report.result.as_ref() == Some(answer),
+        }
+    }
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expected::Unchecked => write!(f, "anything"),
+            Expected::Nothing => write!(f, "nothing"),
+            Expected::Answer(answer) => write!(f, "{answer}"),
+        }
+    }
+}
+
+/// A single example of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// Name shown in test output. Defaults to the file name.
+    pub name: String,
+    pub input: ExampleInput,
+    /// Expected answers of part 1 and 2.
+    pub answers: [Expected; 2],
+    /// Puzzle parameters that differ for the example, e.g. a smaller grid size.
+    pub params: Params,
+}
+
+impl Example {
+    /// Reads the input of the example.
+    pub fn read_input(&self, year: Year) -> Result<String, Error> {
+        match &self.input {
+            ExampleInput::File(file) => {
+                let path = year.data_dir().join("examples").join(file);
+                fs::read_to_string(&path).map_err(Error::io(path))
+            }
+            ExampleInput::Inline(input) => Ok(input.clone()),
+        }
+    }
+}
+
+/// The examples of a day.
+/// Can be deserialized from JSON.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Examples {
+    pub data: Vec<Example>,
+}
+
+impl Examples {
+    /// Path of a day's manifest.
+    pub fn file_path(year: Year, day: Day) -> PathBuf {
+        year.data_dir().join("examples").join(format!("{day}.json"))
+    }
+
+    /// Reads the manifest of a day. Returns `None` if the day does not have one.
+    pub fn read(year: Year, day: Day) -> Result<Option<Self>, Error> {
+        let path = Self::file_path(year, day);
+
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(Error::io(path)(e)),
+        };
+
+        Self::parse(&content, day)
+            .map(Some)
+            .map_err(|e| Error::io(path)(io::Error::new(io::ErrorKind::InvalidData, e)))
+    }
+
+    /// Writes the manifest of a day.
+    pub fn write(&self, year: Year, day: Day) -> Result<(), Error> {
+        let path = Self::file_path(year, day);
+        let json = JsonValue::from(self).format().unwrap();
+        fs::write(&path, json + "\n").map_err(Error::io(path))
+    }
+
+    fn parse(s: &str, day: Day) -> Result<Self, String> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected manifest to be a JSON object.")?;
+
+        let examples = json
+            .get("examples")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected manifest.examples to be an array.")?;
+
+        Ok(Self {
+            data: examples
+                .iter()
+                .map(|example| Example::from_json(example, day))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Reads the input and parameters of the `n`-th example of a day, starting at 1.
+/// Without a manifest, the first example is read from `{day}.txt` and the others from `{day}-{n}.txt`.
+pub fn read_example(year: Year, day: Day, n: usize) -> Result<(String, Params), Error> {
+    let Some(examples) = Examples::read(year, day)? else {
+        let file = if n == 1 {
+            format!("{day}.txt")
+        } else {
+            format!("{day}-{n}.txt")
+        };
+        let path = year.data_dir().join("examples").join(file);
+        let input = fs::read_to_string(&path).map_err(Error::io(path))?;
+        return Ok((input, Params::default()));
+    };
+
+    let example = n
+        .checked_sub(1)
+        .and_then(|index| examples.data.get(index))
+        .ok_or_else(|| {
+            let message = format!(
+                "there is no example {n}, the manifest lists {}.",
+                examples.data.len()
+            );
+            Error::io(Examples::file_path(year, day))(io::Error::new(
+                io::ErrorKind::NotFound,
+                message,
+            ))
+        })?;
+
+    Ok((example.read_input(year)?, example.params.clone()))
+}
+
+/// Adds the examples and expected answers of the current year's stored puzzle description to the manifest of a day.
+/// Example files that already have content and answers that are already set are kept,
+/// so this can run again to add the examples of part two once it is unlocked.
+pub fn update_from_puzzle(day: Day) -> Result<(), Error> {
+    let year = Year::current();
+    let puzzle_path = get_puzzle_path(day);
+    let puzzle = fs::read_to_string(&puzzle_path).map_err(Error::io(&puzzle_path))?;
+
+    let previous = Examples::read(year, day)?;
+    let mut examples = previous.clone().unwrap_or_default();
+    let files = examples.merge(&puzzle_parts(&puzzle), day);
+
+    let dir = year.data_dir().join("examples");
+    if !files.is_empty() {
+        fs::create_dir_all(&dir).map_err(Error::io(&dir))?;
+    }
+
+    for (file, input) in files {
+        let path = dir.join(&file);
+        let is_empty = fs::read_to_string(&path).map_or(true, |content| content.trim().is_empty());
+        if is_empty {
+            fs::write(&path, input).map_err(Error::io(&path))?;
+            println!("Wrote example \"{}\"", path.display());
+        }
+    }
+
+    if previous.as_ref() == Some(&examples) || examples.data.is_empty() {
+        return Ok(());
+    }
+
+    examples.write(year, day)?;
+
+    let answers = examples
+        .data
+        .iter()
+        .flat_map(|example| example.answers.iter().filter_map(Expected::answer))
+        .count();
+    println!(
+        "Updated example manifest \"{}\" with {answers} expected answer(s)",
+        Examples::file_path(year, day).display()
+    );
+
+    Ok(())
+}
+
+/// A part of a solution: its number and a function that runs it on an input with parameters.
+pub type Part = (u8, fn(&str, &Params) -> PartReport);
+
+/// Runs parts of a solution on the examples of its manifest and panics with a summary if any answer is wrong.
+pub fn check_examples(year: Year, day: Day, parts: &[Part]) {
+    migrate_legacy_data();
+    let path = Examples::file_path(year, day);
+    let examples = match Examples::read(year, day) {
+        Ok(Some(examples)) => examples,
+        Ok(None) => panic!(
+            "no example manifest at \"{}\", the examples of day {day} are not checked",
+            path.display()
+        ),
+        Err(e) => panic!("could not read example manifest {e}"),
+    };
+
+    let mut failures: Vec<String> = vec![];
+    let mut checked = 0;
+
+    for example in &examples.data {
+        let input = example
+            .read_input(year)
+            .unwrap_or_else(|e| panic!("could not read example \"{}\" {e}", example.name));
+
+        for (part, run) in parts {
+            let expected = &example.answers[usize::from(*part) - 1];
+            if *expected == Expected::Unchecked {
+                continue;
+            }
+            checked += 1;
+
+            let report = run(&input, &example.params);
+            let actual = match (&report.error, &report.result) {
+                (Some(error), _) => error.clone(),
+                (None, Some(result)) => result.clone(),
+                (None, None) => "nothing".into(),
+            };
+
+            if expected.matches(&report) {
+                println!("{} (part {part}): ✔ {actual}", example.name);
+            } else {
+                let failure = format!(
+                    "{} (part {part}): expected {expected}, got {actual}",
+                    example.name
+                );
+                println!("✖ {failure}");
+                failures.push(failure);
+            }
+        }
+    }
+
+    // a freshly scaffolded day lists its example without answers.
+    if checked == 0 {
+        println!(
+            "No expected answers in \"{}\" yet, add them to check the examples.",
+            path.display()
+        );
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{ANSI_BOLD}{} example(s) failed:{ANSI_RESET}\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Examples {
+    /// Adds the examples and answers detected in the parts of a puzzle description.
+    /// The example of part one is stored in `{day}.txt`. Part two reuses it, unless it shows a different example,
+    /// which is stored in `{day}-2.txt`. Returns the example files and their detected content.
+    fn merge(&mut self, parts: &[PuzzlePart], day: Day) -> Vec<(String, String)> {
+        let mut files: Vec<(String, String)> = vec![];
+
+        for (index, part) in parts.iter().take(2).enumerate() {
+            let file = match (&part.example, files.first()) {
+                (Some(example), Some((file, first))) if example == first => file.clone(),
+                (Some(example), _) => {
+                    let file = if index == 0 {
+                        format!("{day}.txt")
+                    } else {
+                        format!("{day}-{}.txt", index + 1)
+                    };
+                    files.push((file.clone(), example.clone()));
+                    file
+                }
+                (None, Some((file, _))) => file.clone(),
+                (None, None) => continue,
+            };
+
+            let example = match self
+                .data
+                .iter()
+                .position(|example| example.input == ExampleInput::File(file.clone()))
+            {
+                Some(position) => &mut self.data[position],
+                None => {
+                    self.data.push(Example {
+                        name: file.clone(),
+                        input: ExampleInput::File(file),
+                        answers: [Expected::Unchecked, Expected::Unchecked],
+                        params: Params::default(),
+                    });
+                    self.data.last_mut().unwrap()
+                }
+            };
+
+            if let (Expected::Unchecked, Some(answer)) = (&example.answers[index], &part.answer) {
+                example.answers[index] = Expected::Answer(answer.clone());
+            }
+        }
+
+        files
+    }
+}
+
+impl From<&Examples> for JsonValue {
+    fn from(value: &Examples) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "examples".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&Example> for JsonValue {
+    fn from(value: &Example) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        match &value.input {
+            ExampleInput::File(file) => {
+                if value.name != *file {
+                    map.insert("name".into(), JsonValue::String(value.name.clone()));
+                }
+                map.insert("file".into(), JsonValue::String(file.clone()));
+            }
+            ExampleInput::Inline(input) => {
+                if value.name != "inline" {
+                    map.insert("name".into(), JsonValue::String(value.name.clone()));
+                }
+                map.insert("input".into(), JsonValue::String(input.clone()));
+            }
+        }
+
+        for (index, answer) in value.answers.iter().enumerate() {
+            map.insert(
+                format!("part_{}", index + 1),
+                match answer {
+                    Expected::Unchecked => JsonValue::Null,
+                    Expected::Nothing => JsonValue::Boolean(false),
+                    Expected::Answer(answer) => to_scalar(answer),
+                },
+            );
+        }
+
+        if !value.params.is_empty() {
+            map.insert(
+                "params".into(),
+                JsonValue::Object(
+                    value
+                        .params
+                        .iter()
+                        .map(|(key, value)| (key.clone(), to_scalar(value)))
+                        .collect(),
+                ),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl Example {
+    fn from_json(value: &JsonValue, day: Day) -> Result<Self, String> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example to be a JSON object.")?;
+
+        let string = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(x)) => Ok(Some(x.clone())),
+            Some(_) => Err(format!("Expected example.{key} to be null or string.")),
+        };
+
+        let answer = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(Expected::Unchecked),
+            Some(JsonValue::Boolean(false)) => Ok(Expected::Nothing),
+            Some(value @ (JsonValue::String(_) | JsonValue::Number(_))) => {
+                Ok(Expected::Answer(scalar(value).unwrap()))
+            }
+            Some(_) => Err(format!(
+                "Expected example.{key} to be a string, number, false or null."
+            )),
+        };
+
+        let input = match (string("file")?, string("input")?) {
+            (Some(_), Some(_)) => {
+                return Err("Expected either example.file or example.input.".into());
+            }
+            (Some(file), None) => ExampleInput::File(file),
+            (None, Some(input)) => ExampleInput::Inline(input),
+            (None, None) => ExampleInput::File(format!("{day}.txt")),
+        };
+
+        let name = string("name")?.unwrap_or_else(|| match &input {
+            ExampleInput::File(file) => file.clone(),
+            ExampleInput::Inline(_) => "inline".into(),
+        });
+
+        let params = match json.get("params") {
+            None | Some(JsonValue::Null) => Params::default(),
+            Some(JsonValue::Object(params)) => params
+                .iter()
+                .map(|(key, value)| {
+                    scalar(value)
+                        .map(|value| (key.clone(), value))
+                        .ok_or(format!(
+                            "Expected example.params.{key} to be a string or number."
+                        ))
+                })
+                .collect::<Result<HashMap<_, _>, _>>()?
+                .into(),
+            Some(_) => return Err("Expected example.params to be an object.".into()),
+        };
+
+        Ok(Self {
+            name,
+            input,
+            answers: [answer("part_1")?, answer("part_2")?],
+            params,
+        })
+    }
+}
+
+/// Converts a string, number or boolean to a string. Integral numbers are formatted without decimals.
+pub(crate) fn scalar(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(x) => Some(x.clone()),
+        #[allow(clippy::cast_possible_truncation)]
+        JsonValue::Number(x) if x.fract() == 0.0 && x.abs() < 1e15 => Some((*x as i64).to_string()),
+        JsonValue::Number(x) => Some(x.to_string()),
+        JsonValue::Boolean(x) => Some(x.to_string()),
+        _ => None,
+    }
+}
+
+/// Inverse of [`scalar`]: integers that JSON numbers can represent exactly are written as numbers, everything else as strings.
+fn to_scalar(value: &str) -> JsonValue {
+    match value.parse::<i64>() {
+        #[allow(clippy::cast_precision_loss)]
+        Ok(x) if x.abs() < 1_000_000_000_000_000 && x.to_string() == value => {
+            JsonValue::Number(x as f64)
+        }
+        _ => JsonValue::String(value.to_string()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ExampleInput, Examples, Expected};
+    use crate::day;
+    use crate::template::aoc_client::PuzzlePart;
+    use tinyjson::JsonValue;
+
+    fn answer(answer: &str) -> Expected {
+        Expected::Answer(answer.into())
+    }
+
+    #[test]
+    fn parses_manifests() {
+        let examples = Examples::parse(
+            r#"{
+                "examples": [
+                    { "part_1": 3, "part_2": "6" },
+                    { "name": "second", "file": "11-2.txt", "part_2": 2, "params": { "iterations": 10 } },
+                    { "input": "1\n2\n", "part_1": null, "part_2": false }
+                ]
+            }"#,
+            day!(11),
+        )
+        .unwrap();
+
+        let [first, second, third] = examples.data.as_slice() else {
+            panic!("expected 3 examples");
+        };
+
+        assert_eq!(first.name, "11.txt");
+        assert_eq!(first.input, ExampleInput::File("11.txt".into()));
+        assert_eq!(first.answers, [answer("3"), answer("6")]);
+
+        assert_eq!(second.name, "second");
+        assert_eq!(second.answers, [Expected::Unchecked, answer("2")]);
+        assert_eq!(second.params.get::<u32>("iterations"), Some(10));
+
+        assert_eq!(third.input, ExampleInput::Inline("1\n2\n".into()));
+        assert_eq!(third.answers, [Expected::Unchecked, Expected::Nothing]);
+    }
+
+    #[test]
+    fn rejects_malformed_manifests() {
+        assert!(Examples::parse("[]", day!(1)).is_err());
+        assert!(Examples::parse(r#"{ "examples": [{ "part_1": [] }] }"#, day!(1)).is_err());
+        assert!(Examples::parse(r#"{ "examples": [{ "part_1": true }] }"#, day!(1)).is_err());
+        assert!(
+            Examples::parse(
+                r#"{ "examples": [{ "file": "a", "input": "b" }] }"#,
+                day!(1)
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn merges_examples_of_puzzle_parts() {
+        let part = |example: Option<&str>, answer: &str| PuzzlePart {
+            example: example.map(str::to_string),
+            answer: Some(answer.into()),
+        };
+
+        // part one is unlocked, the answer in the manifest is kept.
+        let mut examples = Examples::parse(
+            r#"{ "examples": [{ "part_1": 5, "part_2": null }] }"#,
+            day!(7),
+        )
+        .unwrap();
+        let files = examples.merge(&[part(Some("a\n"), "3")], day!(7));
+        assert_eq!(files, vec![("07.txt".to_string(), "a\n".to_string())]);
+        assert_eq!(examples.data[0].answers, [answer("5"), Expected::Unchecked]);
+
+        // part two reuses the example of part one.
+        let mut examples = Examples::default();
+        examples.merge(&[part(Some("a\n"), "3"), part(None, "6")], day!(7));
+        assert_eq!(examples.data.len(), 1);
+        assert_eq!(examples.data[0].answers, [answer("3"), answer("6")]);
+
+        // part two shows a different example.
+        let files = examples.merge(&[part(Some("a\n"), "3"), part(Some("b\n"), "x")], day!(7));
+        assert_eq!(files[1], ("07-2.txt".to_string(), "b\n".to_string()));
+        assert_eq!(
+            examples.data[1].input,
+            ExampleInput::File("07-2.txt".into())
+        );
+        assert_eq!(examples.data[1].answers, [Expected::Unchecked, answer("x")]);
+    }
+
+    #[test]
+    fn serializes_manifests() {
+        let manifest = r#"{
+            "examples": [
+                { "file": "07.txt", "part_1": 3, "part_2": "abc" },
+                { "name": "small", "input": "1\n", "part_1": null, "part_2": false, "params": { "size": 7 } }
+            ]
+        }"#;
+        let examples = Examples::parse(manifest, day!(7)).unwrap();
+        let json = JsonValue::from(&examples).stringify().unwrap();
+        assert_eq!(Examples::parse(&json, day!(7)).unwrap(), examples);
+        assert!(json.contains(r#""part_1":3"#));
+    }
+}
@@ -0,0 +1,314 @@
+/// Append-only history of stored benchmark timings.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{Error, Write},
+    path::PathBuf,
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::timings::{PartTiming, Timing, Timings};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, Year};
+
+fn history_file_path() -> PathBuf {
+    Year::current().data_dir().join("timings-history.jsonl")
+}
+
+/// Environment a set of timings was measured in.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunInfo {
+    /// Hash of the checked out git commit. `None` outside of a git repository.
+    pub commit: Option<String>,
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: bool,
+    /// Output of `rustc --version`.
+    pub toolchain: Option<String>,
+    /// Unix timestamp in seconds.
+    pub timestamp: u64,
+}
+
+impl RunInfo {
+    /// Collects information about the current working tree and toolchain.
+    pub fn current() -> Self {
+        let output = |cmd: &str, args: &[&str]| {
+            Command::new(cmd)
+                .args(args)
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.as_secs())
+            .unwrap_or_default();
+
+        Self {
+            commit: output("git", &["rev-parse", "HEAD"]),
+            dirty: output("git", &["status", "--porcelain"]).is_some_and(|x| !x.is_empty()),
+            toolchain: output("rustc", &["--version"]),
+            timestamp,
+        }
+    }
+}
+
+/// A single line of the history file: the timing of one day, measured in one run.
+#[derive(Clone, Debug)]
+pub struct HistoryRecord {
+    pub info: RunInfo,
+    pub timing: Timing,
+}
+
+/// Appends a record for every day in `timings` to the history file.
+pub fn append(timings: &Timings, info: &RunInfo) -> Result<(), Error> {
+    fs::create_dir_all(Year::current().data_dir())?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(history_file_path())?;
+
+    for timing in &timings.data {
+        let record = HistoryRecord {
+            info: info.clone(),
+            timing: timing.clone(),
+        };
+        let line = JsonValue::from(&record)
+            .stringify()
+            .map_err(|e| Error::other(e.to_string()))?;
+        writeln!(file, "{line}")?;
+    }
+
+    Ok(())
+}
+
+/// Reads all records of a day from the history file, oldest first. Malformed lines are skipped.
+pub fn read_day(day: Day) -> Vec<HistoryRecord> {
+    let content = fs::read_to_string(history_file_path()).unwrap_or_default();
+    parse_day(&content, day)
+}
+
+fn parse_day(content: &str, day: Day) -> Vec<HistoryRecord> {
+    let mut records: Vec<HistoryRecord> = content
+        .lines()
+        .filter_map(|line| line.parse::<HistoryRecord>().ok())
+        .filter(|record| record.timing.day == day)
+        .collect();
+
+    records.sort_by_key(|record| record.info.timestamp);
+    records
+}
+
+pub fn print_history(day: Day, records: &[HistoryRecord]) {
+    println!("{ANSI_BOLD}Day {day} history{ANSI_RESET}");
+
+    if records.is_empty() {
+        println!(
+            "{ANSI_ITALIC}No stored timings. Run `cargo time {day} --store` to record some.{ANSI_RESET}"
+        );
+        return;
+    }
+
+    println!();
+    println!("{ANSI_BOLD}Date | Commit | Toolchain | Part 1 | Part 2{ANSI_RESET}");
+
+    let mut previous: Option<&Timing> = None;
+
+    for record in records {
+        let part = |get: fn(&Timing) -> &Option<PartTiming>| {
+            let Some(current) = get(&record.timing) else {
+                return "-".to_string();
+            };
+
+            match previous.and_then(|p| get(p).as_ref()) {
+                Some(p) if p.nanos > 0.0 => {
+                    let percent = (current.nanos - p.nanos) / p.nanos * 100.0;
+                    format!("{current} ({percent:+.1}%)")
+                }
+                _ => current.to_string(),
+            }
+        };
+
+        let commit = record.info.commit.as_ref().map_or_else(
+            || "-".to_string(),
+            |commit| {
+                let short = &commit[..commit.len().min(7)];
+                if record.info.dirty {
+                    format!("{short}*")
+                } else {
+                    short.to_string()
+                }
+            },
+        );
+
+        println!(
+            "{} | {} | {} | {} | {}",
+            format_date(record.info.timestamp),
+            commit,
+            record.info.toolchain.as_deref().unwrap_or("-"),
+            part(|t| &t.part_1),
+            part(|t| &t.part_2),
+        );
+
+        previous = Some(&record.timing);
+    }
+
+    if records.iter().any(|r| r.info.dirty) {
+        println!("\n{ANSI_ITALIC}* measured with uncommitted changes.{ANSI_RESET}");
+    }
+}
+
+/// Formats a unix timestamp as `YYYY-MM-DD HH:MM` (UTC).
+fn format_date(timestamp: u64) -> String {
+    // see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    #[allow(clippy::cast_possible_wrap)]
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let seconds = timestamp % 86_400;
+
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryRecord> for JsonValue {
+    fn from(value: &HistoryRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        let string = |x: &Option<String>| x.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        map.insert("commit".into(), string(&value.info.commit));
+        map.insert("dirty".into(), JsonValue::Boolean(value.info.dirty));
+        map.insert("toolchain".into(), string(&value.info.toolchain));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.info.timestamp as f64),
+        );
+        map.insert("timing".into(), JsonValue::from(&value.timing));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl FromStr for HistoryRecord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history record to be a JSON object.")?;
+
+        let string = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(x)) => Ok(Some(x.clone())),
+            Some(_) => Err(format!("Expected record.{key} to be null or string.")),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected record.timestamp to be a number.")?;
+
+        let dirty = json
+            .get("dirty")
+            .and_then(|v| v.get::<bool>())
+            .copied()
+            .unwrap_or_default();
+
+        let timing = Timing::try_from(json.get("timing").ok_or("Expected record.timing.")?)?;
+
+        Ok(HistoryRecord {
+            info: RunInfo {
+                commit: string("commit")?,
+                dirty,
+                toolchain: string("toolchain")?,
+                timestamp,
+            },
+            timing,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{HistoryRecord, RunInfo, format_date, parse_day};
+    use crate::day;
+    use crate::template::timings::{PartTiming, Timing};
+    use tinyjson::JsonValue;
+
+    fn record(day: u8, timestamp: u64, nanos: f64) -> HistoryRecord {
+        HistoryRecord {
+            info: RunInfo {
+                commit: Some("0123456789abcdef".into()),
+                dirty: false,
+                toolchain: Some("rustc 1.85.0".into()),
+                timestamp,
+            },
+            timing: Timing {
+                day: crate::template::Day::new(day).unwrap(),
+                parse: None,
+                part_1: Some(PartTiming::new(nanos, 10, None)),
+                part_2: None,
+                total_nanos: nanos,
+            },
+        }
+    }
+
+    #[test]
+    fn roundtrips_records() {
+        let line = JsonValue::from(&record(1, 1_733_000_000, 100.0))
+            .stringify()
+            .unwrap();
+        let parsed = line.parse::<HistoryRecord>().unwrap();
+        assert_eq!(parsed.info, record(1, 1_733_000_000, 100.0).info);
+        assert_eq!(parsed.timing.day, day!(1));
+        assert_eq!(parsed.timing.part_1.unwrap().nanos, 100.0);
+        assert!(parsed.timing.part_2.is_none());
+    }
+
+    #[test]
+    fn reads_records_of_day() {
+        let content = [
+            record(1, 300, 1.0),
+            record(2, 200, 2.0),
+            record(1, 100, 3.0),
+        ]
+        .iter()
+        .map(|r| JsonValue::from(r).stringify().unwrap())
+        .chain(["not json".to_string()])
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        let records = parse_day(&content, day!(1));
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].info.timestamp, 100);
+        assert_eq!(records[1].info.timestamp, 300);
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(0), "1970-01-01 00:00");
+        assert_eq!(format_date(1_733_011_200), "2024-12-01 00:00");
+        assert_eq!(format_date(951_827_696), "2000-02-29 12:34");
+    }
+}
@@ -1,44 +1,154 @@
-use std::{env, fs};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod examples;
 pub mod runner;
 
+pub use answer::{Answer, IntoAnswer};
 pub use day::*;
+pub use error::Error;
+pub use params::Params;
+pub use run_multi::{is_linked, register_solutions, run_linked};
+pub use year::*;
 
+mod answer;
+mod answers;
+mod compare;
 mod day;
+mod error;
+mod history;
+mod params;
 mod readme_benchmarks;
 mod run_multi;
 mod timings;
+mod verify;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file of a year to a string. E.g. like `data/2025/inputs/01.txt`.
+/// Panics if the file cannot be read, see [`try_read_file`] for a fallible version.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file(folder: &str, year: Year, day: Day) -> String {
+    try_read_file(folder, year, day).unwrap_or_else(|e| panic!("could not open input file {e}"))
+}
+
+/// Helper function that reads a text file of a year to a string. E.g. like `data/2025/inputs/01.txt`.
+pub fn try_read_file(folder: &str, year: Year, day: Day) -> Result<String, Error> {
+    let filepath = year.data_dir().join(folder).join(format!("{day}.txt"));
+    fs::read_to_string(&filepath).map_err(Error::io(filepath))
+}
+
+/// Path of the puzzle input of a day, e.g. `data/2025/inputs/01.txt`.
+pub(crate) fn input_path(year: Year, day: Day) -> PathBuf {
+    year.data_dir().join("inputs").join(format!("{day}.txt"))
+}
+
+/// Reads the input of a day. Fails with [`Error::MissingInput`] if the file does not exist or is empty.
+pub fn read_input(year: Year, day: Day) -> Result<String, Error> {
+    let path = input_path(year, day);
+
+    let missing = |is_empty| Error::MissingInput {
+        year,
+        day,
+        path: path.clone(),
+        is_empty,
+    };
+
+    match fs::read_to_string(&path) {
+        Ok(input) if input.trim().is_empty() => Err(missing(true)),
+        Ok(input) => Ok(input),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(missing(false)),
+        Err(e) => Err(Error::io(&path)(e)),
+    }
+}
+
+/// Files and directories of `data/` from before data was scoped by year.
+const LEGACY_DATA: [&str; 5] = [
+    "inputs",
+    "examples",
+    "puzzles",
+    "timings.json",
+    "answers.json",
+];
+
+/// Moves data from the layout before data was scoped by year, e.g. `data/inputs/01.txt`,
+/// into the directory of the configured year, e.g. `data/2025/inputs/01.txt`.
+/// Files that already exist in the year's directory are kept, and their legacy copy stays in place.
+pub fn migrate_legacy_data() {
+    let root = Path::new("data");
+    let target = Year::configured().data_dir();
+
+    match move_legacy_data(root, &target) {
+        Ok(moved) if moved.is_empty() => {}
+        Ok(moved) => eprintln!(
+            "Moved {} file(s) from \"{}\" to \"{}\".",
+            moved.len(),
+            root.display(),
+            target.display()
+        ),
+        Err(e) => eprintln!("failed to move data to \"{}\": {e}", target.display()),
+    }
+}
+
+/// Moves the [`LEGACY_DATA`] in `root` to `target` and returns the new paths of the moved files.
+fn move_legacy_data(root: &Path, target: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut moved = vec![];
+
+    for name in LEGACY_DATA {
+        let legacy = root.join(name);
+
+        if legacy.is_dir() {
+            let dir = target.join(name);
+            fs::create_dir_all(&dir).map_err(Error::io(&dir))?;
+
+            for entry in fs::read_dir(&legacy).map_err(Error::io(&legacy))? {
+                let from = entry.map_err(Error::io(&legacy))?.path();
+                let to = dir.join(from.file_name().unwrap_or_default());
+                if from.is_file() && !to.exists() && from.file_name() != Some(".keep".as_ref()) {
+                    fs::rename(&from, &to).map_err(Error::io(&from))?;
+                    moved.push(to);
+                }
+            }
+        } else if legacy.is_file() {
+            let to = target.join(name);
+            if !to.exists() {
+                fs::create_dir_all(target).map_err(Error::io(target))?;
+                fs::rename(&legacy, &to).map_err(Error::io(&legacy))?;
+                moved.push(to);
+            }
+        }
+    }
+
+    Ok(moved)
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
+pub fn read_file_part(folder: &str, year: Year, day: Day, part: u8) -> String {
+    let filepath = year
+        .data_dir()
         .join(folder)
         .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    fs::read_to_string(&filepath)
+        .map_err(Error::io(filepath))
+        .unwrap_or_else(|e| panic!("could not open input file {e}"))
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `YEAR` and `DAY` and sets up the input and runner for each part.
+/// The year is read from the solution's file name, e.g. `src/bin/2025-01.rs`.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// With `parse = <fn>`, the input is parsed once and both parts receive the parsed input, e.g.
+/// `solution!(11, parse = parse)` with `fn parse(input: &str) -> Graph` and `fn part_one(graph: &Graph) -> Option<u64>`.
+/// The parse step is timed and reported on its own, see [`Parse`](crate::template::runner::Parse).
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +160,140 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@parsed $day, $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, parse = $parse:expr) => {
+        $crate::solution!(@parsed $day, $parse, [part_one, 1]);
+    };
+    ($day:expr, 2, parse = $parse:expr) => {
+        $crate::solution!(@parsed $day, $parse, [part_two, 2]);
+    };
+
+    (@common $day:expr) => {
+        /// The year of this solution.
+        const YEAR: $crate::template::Year = $crate::template::Year::from_bin_path(file!());
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
+    };
+
+    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $day);
+
+        /// This day's solution, linked into the runner binary by `build.rs`.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::runner::Solution = $crate::template::runner::Solution {
+            year: YEAR,
+            day: DAY,
+            run: |input, params, bench| {
+                vec![$( $crate::template::runner::execute_part($func, input, params, $part, bench), )*]
+            },
+        };
+
+        /// Checks the parts against the examples in `data/{year}/examples/{day}.json`.
+        #[cfg(test)]
+        #[test]
+        fn test_examples() {
+            $crate::template::examples::check_examples(YEAR, DAY, &[$(
+                ($part, |input, params| $crate::template::runner::execute_part($func, input, params, $part, None)),
+            )*]);
+        }
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            $crate::template::Year::set_current(YEAR);
+            let (input, params) = load_input(YEAR, DAY);
+            $( run_part($func, input.as_str(), &params, DAY, $part); )*
         }
     };
+
+    (@parsed $day:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $day);
+
+        /// This day's solution, linked into the runner binary by `build.rs`.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::runner::Solution = $crate::template::runner::Solution {
+            year: YEAR,
+            day: DAY,
+            run: |input, params, bench| {
+                $crate::template::runner::execute_parsed($parse, input, params, &[$($part),*], bench, |parsed| {
+                    vec![$( $crate::template::runner::execute_part($func, parsed, params, $part, bench), )*]
+                })
+            },
+        };
+
+        /// Checks the parts against the examples in `data/{year}/examples/{day}.json`.
+        #[cfg(test)]
+        #[test]
+        fn test_examples() {
+            $crate::template::examples::check_examples(YEAR, DAY, &[$(
+                ($part, |input, params| {
+                    let mut reports = $crate::template::runner::execute_parsed($parse, input, params, &[$part], None, |parsed| {
+                        vec![$crate::template::runner::execute_part($func, parsed, params, $part, None)]
+                    });
+                    reports.pop().unwrap()
+                }),
+            )*]);
+        }
+
+        fn main() {
+            use $crate::template::runner::*;
+            $crate::template::Year::set_current(YEAR);
+            let (input, params) = load_input(YEAR, DAY);
+            run_parsed($parse, &input, &params, &[$($part),*], |parsed| {
+                $( run_part($func, parsed, &params, DAY, $part); )*
+            });
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::move_legacy_data;
+    use std::{env, fs, process};
+
+    #[test]
+    fn moves_legacy_data_into_the_year() {
+        let root = env::temp_dir().join(format!("aoc-legacy-{}", process::id()));
+        let target = root.join("2025");
+        fs::create_dir_all(root.join("inputs")).unwrap();
+        fs::create_dir_all(target.join("inputs")).unwrap();
+        fs::write(root.join("inputs/01.txt"), "legacy 1").unwrap();
+        fs::write(root.join("inputs/02.txt"), "legacy 2").unwrap();
+        fs::write(root.join("inputs/.keep"), "").unwrap();
+        fs::write(target.join("inputs/02.txt"), "current 2").unwrap();
+        fs::write(root.join("timings.json"), "{}").unwrap();
+
+        let moved = move_legacy_data(&root, &target);
+        let read = |path: &str| fs::read_to_string(root.join(path)).ok();
+        let contents = [
+            read("2025/inputs/01.txt"),
+            read("2025/inputs/02.txt"),
+            read("inputs/02.txt"),
+            read("2025/timings.json"),
+            read("2025/inputs/.keep"),
+        ];
+        let moved_again = move_legacy_data(&root, &target).map(|moved| moved.len());
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(moved.unwrap().len(), 2);
+        assert_eq!(
+            contents,
+            [
+                Some("legacy 1".into()),
+                Some("current 2".into()),
+                Some("legacy 2".into()),
+                Some("{}".into()),
+                None
+            ]
+        );
+        assert_eq!(moved_again.unwrap(), 0);
+    }
 }
@@ -0,0 +1,89 @@
+use std::{collections::HashMap, fmt::Debug, str::FromStr};
+
+/// Puzzle parameters of an input, e.g. the number of iterations or the size of a grid.
+/// Examples set them in their manifest, see [`examples`](crate::template::examples).
+/// Parts read them with a default that applies to the real input:
+///
+/// ```
+/// # use advent_of_code::template::Params;
+/// pub fn part_one(input: &str, params: &Params) -> Option<usize> {
+///     let iterations: usize = params.get_or("iterations", 1_000);
+///     Some(input.len() * iterations)
+/// }
+///
+/// assert_eq!(part_one("abc", &Params::default()), Some(3_000));
+/// assert_eq!(part_one("abc", &Params::from([("iterations", "10")])), Some(30));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(HashMap<String, String>);
+
+impl Params {
+    /// Returns the value of `key` parsed as `T`, or `None` if the input does not set it.
+    /// Panics if the value cannot be parsed.
+    pub fn get<T: FromStr>(&self, key: &str) -> Option<T>
+    where
+        T::Err: Debug,
+    {
+        self.0.get(key).map(|value| {
+            value
+                .parse()
+                .unwrap_or_else(|e| panic!("invalid value \"{value}\" of param \"{key}\": {e:?}"))
+        })
+    }
+
+    /// Returns the value of `key` parsed as `T`, or `default` if the input does not set it.
+    /// Panics if the value cannot be parsed.
+    pub fn get_or<T: FromStr>(&self, key: &str, default: T) -> T
+    where
+        T::Err: Debug,
+    {
+        self.get(key).unwrap_or(default)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.0.iter()
+    }
+}
+
+impl From<HashMap<String, String>> for Params {
+    fn from(value: HashMap<String, String>) -> Self {
+        Self(value)
+    }
+}
+
+impl<K: Into<String>, V: Into<String>, const N: usize> From<[(K, V); N]> for Params {
+    fn from(value: [(K, V); N]) -> Self {
+        Self(
+            value
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Params;
+
+    #[test]
+    fn reads_typed_params() {
+        let params = Params::from([("iterations", "10"), ("name", "abc")]);
+        assert_eq!(params.get::<u32>("iterations"), Some(10));
+        assert_eq!(params.get_or("iterations", 1_000_u64), 10);
+        assert_eq!(params.get_or("size", 7_u8), 7);
+        assert_eq!(params.get::<String>("name").as_deref(), Some("abc"));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid value \"abc\" of param \"name\"")]
+    fn panics_on_invalid_params() {
+        Params::from([("name", "abc")]).get::<u32>("name");
+    }
+}
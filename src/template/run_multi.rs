@@ -1,40 +1,214 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    env,
+    process::{self, Command},
+    sync::{
+        OnceLock,
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::template::runner::{BenchConfig, Limits, PartReport, Solution, print_report};
+use crate::template::{self, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, Year};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+static SOLUTIONS: OnceLock<&'static [Solution]> = OnceLock::new();
+
+/// Registers the solutions that are linked into the runner binary.
+/// Registered days are executed in-process instead of through `cargo run`.
+pub fn register_solutions(solutions: &'static [Solution]) {
+    let _ = SOLUTIONS.set(solutions);
+}
+
+/// Whether solutions are linked into this binary, i.e. whether it is the linked runner.
+pub fn is_linked() -> bool {
+    SOLUTIONS.get().is_some()
+}
+
+/// Name of the runner binary that links all solutions, see `src/linked.rs`.
+const LINKED_RUNNER: &str = "advent_of_code_linked";
+
+/// Runs the current command in the linked runner, which executes registered days in-process.
+/// The linked runner is built with the optimization level of the solutions, so that they are used, see [`registered_solution`].
+/// Returns `false` if the linked runner cannot be built, e.g. because a solution does not compile yet.
+/// The command then runs every day through `cargo run` instead. Exits with the status of the linked runner if it fails.
+pub fn run_linked(is_release: bool) -> Result<bool, template::Error> {
+    let mut build_args = vec!["build", "--quiet", "--bin", LINKED_RUNNER];
+    if is_release {
+        build_args.push("--release");
+    }
+
+    let command = format!("cargo {}", build_args.join(" "));
+    let output = Command::new("cargo")
+        .args(&build_args)
+        .output()
+        .map_err(|source| template::Error::Command {
+            command: command.clone(),
+            source,
+        })?;
+
+    if !output.status.success() {
+        eprintln!(
+            "{ANSI_ITALIC}Solutions could not be linked into the runner, running every day through cargo. Run `{command}` to see why.{ANSI_RESET}"
+        );
+        return Ok(false);
+    }
+
+    let mut run_args: Vec<String> = build_args.iter().map(ToString::to_string).collect();
+    run_args[0] = "run".into();
+    run_args.push("--".into());
+    run_args.extend(env::args().skip(1));
 
-    let mut need_space = false;
+    let command = format!("cargo {}", run_args.join(" "));
+    let status = Command::new("cargo")
+        .args(&run_args)
+        .status()
+        .map_err(|source| template::Error::Command { command, source })?;
 
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+
+    Ok(true)
+}
+
+/// Looks up the registered solution of a day.
+/// Solutions are only used if the runner was built with the requested optimization level.
+fn registered_solution(day: Day, is_release: bool) -> Option<&'static Solution> {
+    if cfg!(debug_assertions) == is_release {
+        return None;
+    }
+
+    let year = Year::current();
+    SOLUTIONS
+        .get()?
+        .iter()
+        .find(|solution| solution.year == year && solution.day == day)
+}
+
+/// Runs the solutions for a set of days. Solutions are benched if a `bench` configuration is passed.
+/// Up to `jobs` days are run concurrently in child processes, their output is printed in order of days.
+/// Benched runs are always sequential.
+/// Days that exceed the `limits` are reported as failed, the remaining days keep running.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    bench: Option<&BenchConfig>,
+    jobs: usize,
+    limits: &Limits,
+) -> Option<Timings> {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    let jobs = if bench.is_some() && jobs > 1 {
+        eprintln!("Benchmarks run sequentially, ignoring --jobs.");
+        1
+    } else {
+        jobs.clamp(1, days.len().max(1))
+    };
+
+    let mut timings: Vec<Timing> = Vec::with_capacity(days.len());
+
+    let print_header = |index: usize, day: Day| {
+        if index > 0 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+    };
+
+    let mut print_reports = |day: Day, reports: Result<Vec<PartReport>, template::Error>| {
+        let reports = match reports {
+            Ok(reports) => reports,
+            Err(template::Error::MissingInput { .. }) => {
+                println!("No input.");
+                return;
+            }
+            // the other days still run, so a day that cannot be started is reported like a failed part.
+            Err(e) => {
+                println!("✖ {ANSI_ITALIC}{e}{ANSI_RESET}");
+                return;
+            }
+        };
+
+        if reports.is_empty() {
+            println!("Not solved.");
+        } else {
+            for report in &reports {
+                print_report(report);
+            }
+            timings.push(child_commands::parse_exec_time(&reports, day));
+        }
+    };
+
+    if jobs == 1 {
+        for (index, day) in days.iter().enumerate() {
+            // print the header first, so that output of the solution is forwarded below it.
+            print_header(index, *day);
+            print_reports(
+                *day,
+                child_commands::run_solution(*day, bench, is_release, limits),
+            );
+        }
+    } else {
+        let next = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel::<(usize, DayRun)>();
+
+        thread::scope(|scope| {
+            for _ in 0..jobs {
+                let sender = sender.clone();
+                let (days, next) = (&days, &next);
+
+                scope.spawn(move || {
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(day) = days.get(index) else {
+                            break;
+                        };
+                        let mut output = vec![];
+                        let reports = child_commands::run_solution_buffered(
+                            *day,
+                            is_release,
+                            limits,
+                            &mut output,
+                        );
+                        let run = DayRun { reports, output };
+                        if sender.send((index, run)).is_err() {
+                            break;
+                        }
+                    }
+                });
             }
-            need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+            drop(sender);
+
+            // print days as soon as all previous days have been printed.
+            let mut pending: BTreeMap<usize, DayRun> = BTreeMap::new();
+            let mut printed = 0;
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            for (index, run) in receiver {
+                pending.insert(index, run);
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+                while let Some(run) = pending.remove(&printed) {
+                    print_header(printed, days[printed]);
+                    for line in &run.output {
+                        println!("{line}");
+                    }
+                    print_reports(days[printed], run.reports);
+                    printed += 1;
+                }
             }
         });
+    }
 
-    if is_timed {
+    if bench.is_some() {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -46,149 +220,282 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
+/// Reports of a single day together with the output the solution printed while running.
+struct DayRun {
+    reports: Result<Vec<PartReport>, template::Error>,
+    output: Vec<String>,
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
+#[must_use]
+pub fn get_path_for_bin(day: Day) -> String {
+    format!("./src/bin/{}.rs", get_bin_name(day))
 }
 
+/// Name of a day's solution bin in the current year, e.g. `2025-01`.
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_bin_name(day: Day) -> String {
+    format!("{}-{day}", Year::current())
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as parsing their reports.
+/// Solutions that are registered with the runner are executed in-process instead.
 pub mod child_commands {
-    use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use super::{get_bin_name, get_path_for_bin, registered_solution};
+    use crate::template::error::MISSING_INPUT_EXIT_CODE;
+    use crate::template::runner::{
+        BenchConfig, InputSource, Limits, OutputFormat, PARSE_PART, PartReport, Solution,
+        format_memory,
+    };
+    use crate::template::timings::PartTiming;
+    use crate::template::{Day, Error, Year, input_path};
     use std::{
-        io::{BufRead, BufReader},
+        io::{self, BufRead, BufReader},
+        panic::{self, AssertUnwindSafe},
         path::Path,
         process::{Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day and collect its machine-readable part reports.
+    /// Solutions with `limits` always run in a child process, so that they can be stopped.
+    pub fn run_solution(
+        day: Day,
+        bench: Option<&BenchConfig>,
+        is_release: bool,
+        limits: &Limits,
+    ) -> Result<Vec<PartReport>, Error> {
+        run(
+            day,
+            bench,
+            is_release,
+            limits,
+            &InputSource::default(),
+            None,
+        )
+    }
+
+    /// Like [`run_solution`], but runs the solution on another `input` than the puzzle input of the day.
+    pub fn run_solution_on(
+        day: Day,
+        input: &InputSource,
+        is_release: bool,
+        limits: &Limits,
+    ) -> Result<Vec<PartReport>, Error> {
+        run(day, None, is_release, limits, input, None)
+    }
+
+    /// Like [`run_solution`], but collects the output of the bin into `output` instead of forwarding it.
+    /// Always runs in a child process, as output that a solution prints in-process cannot be captured.
+    pub fn run_solution_buffered(
+        day: Day,
+        is_release: bool,
+        limits: &Limits,
+        output: &mut Vec<String>,
+    ) -> Result<Vec<PartReport>, Error> {
+        run(
+            day,
+            None,
+            is_release,
+            limits,
+            &InputSource::default(),
+            Some(output),
+        )
+    }
+
+    fn run(
+        day: Day,
+        bench: Option<&BenchConfig>,
+        is_release: bool,
+        limits: &Limits,
+        input: &InputSource,
+        output: Option<&mut Vec<String>>,
+    ) -> Result<Vec<PartReport>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        if !limits.is_set()
+            && output.is_none()
+            && let Some(solution) = registered_solution(day, is_release)
+        {
+            return run_in_process(solution, bench, input);
+        }
+
+        let format = OutputFormat::Json.to_string();
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            get_bin_name(day),
+        ];
 
         if is_release {
-            args.push("--release");
+            args.push("--release".into());
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        args.extend(["--".into(), "--format".into(), format]);
+
+        if let Some(bench) = bench {
+            // mirror `--time` flag and bench configuration to child invocations.
+            args.push("--time".into());
+            args.extend(bench.to_args());
         }
 
+        args.extend(limits.to_args());
+        args.extend(input.to_args());
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward (or buffer) stderr while collecting the reports written to stdout.
+
+        let command = format!("cargo {}", args.join(" "));
+        let failed = |source| Error::Command {
+            command: command.clone(),
+            source,
+        };
 
         let mut cmd = Command::new("cargo")
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .spawn()?;
+            .spawn()
+            .map_err(failed)?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let broken_pipe = || failed(io::ErrorKind::BrokenPipe.into());
+        let stdout = BufReader::new(cmd.stdout.take().ok_or_else(broken_pipe)?);
+        let stderr = BufReader::new(cmd.stderr.take().ok_or_else(broken_pipe)?);
 
-        let mut output = vec![];
+        let is_buffered = output.is_some();
 
         let thread = thread::spawn(move || {
+            let mut buffer = vec![];
             stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+                if is_buffered {
+                    buffer.push(line.unwrap());
+                } else {
+                    eprintln!("{}", line.unwrap());
+                }
             });
+            buffer
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let mut reports = match output {
+            Some(output) => {
+                let reports = collect_reports(stdout.lines().map(Result::unwrap), |line| {
+                    output.push(line);
+                });
+                output.extend(thread.join().unwrap());
+                reports
+            }
+            None => {
+                let reports = parse_reports(stdout.lines().map(Result::unwrap));
+                thread.join().unwrap();
+                reports
+            }
+        };
+
+        let status = cmd.wait().map_err(failed)?;
+
+        // the solution already printed why, the error only tells the caller which input was missing.
+        if status.code() == Some(MISSING_INPUT_EXIT_CODE) {
+            let (year, path) = (Year::current(), input_path(Year::current(), day));
+            return Err(Error::MissingInput {
+                year,
+                day,
+                is_empty: path.exists(),
+                path,
+            });
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        // allocations beyond the memory limit abort the solution, report the part that was running.
+        if let Some(memory) = limits.memory
+            && status.code().is_none()
+        {
+            let part = reports.last().map_or(1, |report| report.part + 1);
+            reports.push(PartReport::failed(
+                part,
+                format!(
+                    "aborted, likely by exceeding the memory limit of {}",
+                    format_memory(memory)
+                ),
+            ));
+        }
+
+        Ok(reports)
+    }
+
+    /// Run a registered solution on the day's input. A panicking solution is reported as not solved.
+    fn run_in_process(
+        solution: &Solution,
+        bench: Option<&BenchConfig>,
+        input: &InputSource,
+    ) -> Result<Vec<PartReport>, Error> {
+        let (input, params) = match input.read(solution.year, solution.day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("{e}");
+                return match e {
+                    Error::MissingInput { .. } => Err(e),
+                    _ => Ok(vec![]),
+                };
+            }
+        };
+
+        Ok(
+            panic::catch_unwind(AssertUnwindSafe(|| (solution.run)(&input, &params, bench)))
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Parse part reports from the output of a solution bin. Lines that are not reports are forwarded to stdout.
+    pub fn parse_reports(lines: impl Iterator<Item = String>) -> Vec<PartReport> {
+        collect_reports(lines, |line| println!("{line}"))
+    }
 
-        Ok(output)
+    fn collect_reports(
+        lines: impl Iterator<Item = String>,
+        mut other: impl FnMut(String),
+    ) -> Vec<PartReport> {
+        lines
+            .filter_map(|line| match line.parse::<PartReport>() {
+                Ok(report) => Some(report),
+                Err(_) => {
+                    other(line);
+                    None
+                }
+            })
+            .collect()
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    pub fn parse_exec_time(reports: &[PartReport], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
         };
 
-        output
+        reports
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+            .filter(|report| report.result.is_some() || report.is_parsed())
+            .for_each(|report| {
+                let timing = PartTiming::new(report.nanos, report.samples, report.stats);
+
+                match report.part {
+                    PARSE_PART => timings.parse = Some(timing),
+                    1 => timings.part_1 = Some(timing),
+                    2 => timings.part_2 = Some(timing),
+                    _ => {}
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
-
-                timings.total_nanos += nanos;
+                timings.total_nanos += report.nanos;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .next_back()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -205,50 +512,57 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_exec_time, parse_reports};
 
         use crate::day;
 
         #[test]
         fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
+            let reports = parse_reports(
+                [
+                    r#"{"part":1,"result":"0","nanos":74.13,"samples":100000,"error":null}"#,
+                    r#"{"part":2,"result":"10","nanos":74130000,"samples":99999,"error":null}"#,
+                    "",
+                ]
+                .map(String::from)
+                .into_iter(),
             );
+            let res = parse_exec_time(&reports, day!(1));
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.part_1.unwrap().to_string(), "74.0ns");
+            assert_eq!(res.part_2.as_ref().unwrap().samples, Some(99999));
+            assert_eq!(res.part_2.unwrap().to_string(), "74.1ms");
         }
 
         #[test]
         fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
+            let reports = parse_reports(
+                [
+                    r#"{"part":1,"result":"@ @ @ ( ) ms","nanos":2000000000,"samples":5,"error":null}"#,
+                    "Part 2: 10s (100ms @ 1 samples)",
+                    r#"{"part":2,"result":"10s","nanos":100000000,"samples":1,"error":null}"#,
+                ]
+                .map(String::from)
+                .into_iter(),
             );
+            let res = parse_exec_time(&reports, day!(1));
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap().to_string(), "2.0s");
+            assert_eq!(res.part_2.unwrap().to_string(), "100.0ms");
         }
 
         #[test]
         fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
+            let reports = parse_reports(
+                [
+                    r#"{"part":1,"result":null,"nanos":10,"samples":1,"error":null}"#,
+                    r#"{"part":2,"result":null,"nanos":10,"samples":1,"error":null}"#,
+                    "",
+                ]
+                .map(String::from)
+                .into_iter(),
             );
+            let res = parse_exec_time(&reports, day!(1));
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
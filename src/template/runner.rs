@@ -1,35 +1,574 @@
 /// Encapsulates code that interacts with solution functions.
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{Write, stdout};
-use std::process::Output;
+use std::io::{self, Read, Write, stdout};
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Once;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, iter, process, thread};
+
+use tinyjson::JsonValue;
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::answer::IntoAnswer;
+use crate::template::answers::Answers;
+use crate::template::aoc_client::{self, AocClientError, Hint, SubmissionOutcome};
+use crate::template::error::TIMEOUT_EXIT_CODE;
+use crate::template::examples::read_example;
+use crate::template::{
+    ANSI_ITALIC, ANSI_RESET, Answer, Day, Error, Params, Year, migrate_legacy_data, read_input,
+};
+
+/// Output format of the runner, selected with `--format <human|json>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Human,
+    /// One JSON object per part and line, see [`PartReport`].
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(OutputFormat::Human),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!(
+                "unknown output format \"{s}\", expecting human or json."
+            )),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Human => f.write_str("human"),
+            OutputFormat::Json => f.write_str("json"),
+        }
+    }
+}
+
+/// Reads the output format from the `--format` argument. Defaults to [`OutputFormat::Human`].
+fn output_format() -> OutputFormat {
+    let args: Vec<String> = env::args().collect();
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+    let Some(index) = args.iter().position(|x| x == "--format") else {
+        return OutputFormat::default();
+    };
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    match args.get(index + 1).map(|x| x.parse()) {
+        Some(Ok(format)) => format,
+        Some(Err(e)) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+        None => {
+            eprintln!("Unexpected command-line input. Format: --format <human|json>");
+            process::exit(1);
+        }
+    }
+}
+
+/// Machine-readable result of running one part of a solution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartReport {
+    /// Number of the part, or [`PARSE_PART`] for the parse step.
+    pub part: u8,
+    pub result: Option<String>,
+    /// Execution time in nanoseconds. When benched, this is the average of all samples.
+    pub nanos: f64,
+    pub samples: u128,
+    /// Statistics of the benchmark, if the part was benched.
+    pub stats: Option<BenchStats>,
+    /// Why the part failed, e.g. the message and location of a panic or a timeout.
+    pub error: Option<String>,
+}
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+impl PartReport {
+    fn new<T: Display>(
+        part: u8,
+        result: Option<&T>,
+        duration: &Duration,
+        samples: u128,
+        stats: Option<BenchStats>,
+    ) -> Self {
+        Self {
+            part,
+            result: result.map(ToString::to_string),
+            #[allow(clippy::cast_precision_loss)]
+            nanos: duration.as_nanos() as f64,
+            samples,
+            stats,
+            error: None,
+        }
+    }
+
+    /// Report of a part that failed to produce a result, e.g. because it panicked.
+    pub(crate) fn failed(part: u8, error: String) -> Self {
+        Self {
+            part,
+            result: None,
+            nanos: 0.0,
+            samples: 0,
+            stats: None,
+            error: Some(error),
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(self.nanos as u64)
+    }
+
+    /// Whether this is the report of a successful parse step.
+    pub fn is_parsed(&self) -> bool {
+        self.part == PARSE_PART && self.error.is_none()
+    }
+}
+
+/// Number of the parse step in [`PartReport`]s of solutions with a [`Parse`] function.
+pub const PARSE_PART: u8 = 0;
+
+/// Error of parts that did not run because parsing their input failed.
+const PARSE_FAILED: &str = "not run, parsing the input failed";
+
+/// Label of a part in the human-readable output, e.g. `Part 1` or `Parse`.
+pub(crate) fn part_label(part: u8) -> String {
+    if part == PARSE_PART {
+        "Parse".into()
+    } else {
+        format!("Part {part}")
+    }
+}
+
+/// A part of a solution. Implemented for functions that take the input, and optionally the [`Params`] of the input:
+/// `fn part_one(input: &str) -> Option<T>` or `fn part_one(input: &str, params: &Params) -> Option<T>`.
+/// With a [`Parse`] function, the input is the parsed input instead, e.g. `fn part_one(grid: &Grid) -> Option<T>`.
+/// The result `T` is anything that converts into an [`Answer`] with [`IntoAnswer`], i.e. any [`Display`] type.
+/// The `Marker` type parameter tells the two apart and is inferred.
+pub trait Part<Input: ?Sized, Marker> {
+    fn run(&self, input: &Input, params: &Params) -> Option<Answer>;
+}
+
+impl<F, I: ?Sized, T: IntoAnswer> Part<I, fn(&I) -> T> for F
+where
+    F: Fn(&I) -> Option<T>,
+{
+    fn run(&self, input: &I, _params: &Params) -> Option<Answer> {
+        self(input).map(IntoAnswer::into_answer)
+    }
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+impl<F, I: ?Sized, T: IntoAnswer> Part<I, fn(&I, &Params) -> T> for F
+where
+    F: Fn(&I, &Params) -> Option<T>,
+{
+    fn run(&self, input: &I, params: &Params) -> Option<Answer> {
+        self(input, params).map(IntoAnswer::into_answer)
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Parses the input of a solution once for both parts, see [`solution!`](crate::solution).
+/// Implemented for `fn parse(input: &str) -> T` and `fn parse(input: &str, params: &Params) -> T`.
+/// The parsed input must not borrow from the input.
+pub trait Parse<Marker> {
+    type Output;
+
+    fn parse(&self, input: &str, params: &Params) -> Self::Output;
+}
+
+impl<F, T> Parse<fn(&str) -> T> for F
+where
+    F: Fn(&str) -> T,
+{
+    type Output = T;
+
+    fn parse(&self, input: &str, _params: &Params) -> T {
+        self(input)
+    }
+}
+
+impl<F, T> Parse<fn(&str, &Params) -> T> for F
+where
+    F: Fn(&str, &Params) -> T,
+{
+    type Output = T;
+
+    fn parse(&self, input: &str, params: &Params) -> T {
+        self(input, params)
+    }
+}
+
+fn bench_config() -> Option<BenchConfig> {
+    env::args()
+        .any(|x| x == "--time")
+        .then(BenchConfig::from_args)
+}
+
+/// Applies the `--memory` limit and watches the `--timeout` of a part, see [`Limits`].
+fn enforce_limits(part: u8, format: OutputFormat) -> Option<Watchdog> {
+    let limits = Limits::from_args();
+    if let Some(memory) = limits.memory {
+        limit_memory(memory);
+    }
+
+    limits.timeout.map(|timeout| {
+        Watchdog::start(timeout, move || {
+            print_failure(part, &format!("timed out after {timeout:.1?}"), format);
+            process::exit(TIMEOUT_EXIT_CODE);
+        })
+    })
+}
+
+fn print_failure(part: u8, error: &str, format: OutputFormat) {
+    match format {
+        OutputFormat::Human => print_error(&part_label(part), error),
+        OutputFormat::Json => {
+            let report = PartReport::failed(part, error.to_string());
+            println!("{}", JsonValue::from(&report).stringify().unwrap());
+        }
+    }
+}
+
+pub fn run_part<I: ?Sized, M>(
+    func: impl Part<I, M>,
+    input: &I,
+    params: &Params,
+    day: Day,
+    part: u8,
+) {
+    let format = output_format();
+    let part_str = part_label(part);
+    let bench = bench_config();
+    // the timeout applies to the run that produces the answer, not to benching it.
+    let watchdog = Cell::new(enforce_limits(part, format));
+
+    let timed = catch_panic(|| {
+        run_timed(
+            |input: &I| func.run(input, params),
+            input,
+            |result| {
+                drop(watchdog.take());
+                if format == OutputFormat::Human {
+                    print_result(result, &part_str, "");
+                }
+            },
+            bench.as_ref(),
+            format == OutputFormat::Human,
+        )
+    });
+
+    let (result, duration, samples, stats) = match timed {
+        Ok(timed) => timed,
+        Err(error) => {
+            print_failure(part, &error, format);
+            return;
+        }
+    };
+
+    match format {
+        OutputFormat::Human => {
+            print_result(&result, &part_str, &format_duration(&duration, samples));
+            if let Some(stats) = &stats {
+                print_stats(stats);
+            }
+        }
+        OutputFormat::Json => {
+            let report = PartReport::new(part, result.as_ref(), &duration, samples, stats);
+            println!("{}", JsonValue::from(&report).stringify().unwrap());
+        }
+    }
+
+    if let Some(result) = result
+        && let Some(outcome) = submit_result(&result, day, part)
+    {
+        print_submission(outcome);
+    }
+}
+
+/// Parses the input once and prints the time it took as its own row, before `run_parts` runs the parts on it.
+/// If parsing fails, the `parts` are reported as failed instead.
+pub fn run_parsed<M, P: Parse<M>>(
+    parse: P,
+    input: &str,
+    params: &Params,
+    parts: &[u8],
+    run_parts: impl FnOnce(&P::Output),
+) {
+    let format = output_format();
+    let bench = bench_config();
+    let watchdog = Cell::new(enforce_limits(PARSE_PART, format));
+
+    let timed = catch_panic(|| {
+        run_timed(
+            |input: &str| parse.parse(input, params),
+            input,
+            |_| {
+                drop(watchdog.take());
+                if format == OutputFormat::Human {
+                    print_parse("");
+                }
+            },
+            bench.as_ref(),
+            format == OutputFormat::Human,
+        )
+    });
+
+    drop(watchdog.take());
+
+    let (parsed, duration, samples, stats) = match timed {
+        Ok(timed) => timed,
+        Err(error) => {
+            print_failure(PARSE_PART, &error, format);
+            for part in parts {
+                print_failure(*part, PARSE_FAILED, format);
+            }
+            return;
+        }
+    };
+
+    match format {
+        OutputFormat::Human => {
+            print_parse(&format_duration(&duration, samples));
+            if let Some(stats) = &stats {
+                print_stats(stats);
+            }
+        }
+        OutputFormat::Json => {
+            let report = PartReport::new::<String>(PARSE_PART, None, &duration, samples, stats);
+            println!("{}", JsonValue::from(&report).stringify().unwrap());
+        }
+    }
+
+    run_parts(&parsed);
+}
+
+/// Input a solution runs on, selected with the `--input <path|->` and `--example [<n>]` arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in `data/{year}/inputs`. If `download` is set, a missing input is downloaded first.
+    Puzzle { download: bool },
+    /// A file, or stdin if the path is `-`.
+    File(PathBuf),
+    /// The `n`-th example of the day, starting at 1. See [`read_example`].
+    Example(usize),
+}
+
+impl Default for InputSource {
+    fn default() -> Self {
+        InputSource::Puzzle { download: false }
+    }
+}
+
+impl InputSource {
+    fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let index_of = |flag: &str| args.iter().position(|x| x == flag);
+
+        if let Some(index) = index_of("--input") {
+            let Some(path) = args.get(index + 1) else {
+                eprintln!("Unexpected command-line input. Format: --input <path|->");
+                process::exit(1);
+            };
+            InputSource::File(path.into())
+        } else if let Some(index) = index_of("--example") {
+            let n = args.get(index + 1).and_then(|x| x.parse().ok());
+            InputSource::Example(n.unwrap_or(1))
+        } else {
+            InputSource::Puzzle {
+                download: index_of("--download").is_some(),
+            }
+        }
+    }
+
+    /// Arguments that select this input in a child invocation.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle { download: false } => vec![],
+            InputSource::Puzzle { download: true } => vec!["--download".into()],
+            InputSource::File(path) => vec!["--input".into(), path.display().to_string()],
+            InputSource::Example(n) => vec!["--example".into(), n.to_string()],
+        }
+    }
+
+    /// Reads the input and its parameters.
+    pub fn read(&self, year: Year, day: Day) -> Result<(String, Params), Error> {
+        match self {
+            InputSource::Puzzle { download } => {
+                let mut input = read_input(year, day);
+
+                if *download && matches!(input, Err(Error::MissingInput { .. })) {
+                    match aoc_client::download(day) {
+                        Ok(()) => input = read_input(year, day),
+                        Err(e) => eprintln!("failed to download input for day {day}: {e}"),
+                    }
+                }
+
+                Ok((input?, Params::default()))
+            }
+            InputSource::File(path) if path.as_os_str() == "-" => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(Error::io(path))?;
+                Ok((input, Params::default()))
+            }
+            InputSource::File(path) => {
+                let input = fs::read_to_string(path).map_err(Error::io(path))?;
+                Ok((input, Params::default()))
+            }
+            InputSource::Example(n) => read_example(year, day, *n),
+        }
+    }
+}
+
+/// Reads the input of a day before running its parts, see [`InputSource`].
+/// Exits with a distinct status if the puzzle input is missing, see [`Error::exit_code`].
+pub fn load_input(year: Year, day: Day) -> (String, Params) {
+    migrate_legacy_data();
+    let source = InputSource::from_args();
+
+    let input = source.read(year, day).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(e.exit_code());
+    });
+
+    if output_format() == OutputFormat::Human {
+        match &source {
+            InputSource::Puzzle { .. } => {}
+            InputSource::File(path) if path.as_os_str() == "-" => {
+                println!("{ANSI_ITALIC}Input: stdin{ANSI_RESET}");
+            }
+            InputSource::File(path) => {
+                println!("{ANSI_ITALIC}Input: {}{ANSI_RESET}", path.display());
+            }
+            InputSource::Example(n) => println!("{ANSI_ITALIC}Input: example {n}{ANSI_RESET}"),
+        }
+    }
+
+    input
+}
+
+/// Runs a part without printing its result and returns its report. The part is benched if `bench` is passed.
+pub fn execute_part<I: ?Sized, M>(
+    func: impl Part<I, M>,
+    input: &I,
+    params: &Params,
+    part: u8,
+    bench: Option<&BenchConfig>,
+) -> PartReport {
+    let func = |input: &I| func.run(input, params);
+    match catch_panic(|| run_timed(func, input, |_| {}, bench, false)) {
+        Ok((result, duration, samples, stats)) => {
+            PartReport::new(part, result.as_ref(), &duration, samples, stats)
+        }
+        Err(error) => PartReport::failed(part, error),
+    }
+}
+
+/// Like [`run_parsed`], but returns the reports of the parse step and the parts instead of printing them.
+pub fn execute_parsed<M, P: Parse<M>>(
+    parse: P,
+    input: &str,
+    params: &Params,
+    parts: &[u8],
+    bench: Option<&BenchConfig>,
+    run_parts: impl FnOnce(&P::Output) -> Vec<PartReport>,
+) -> Vec<PartReport> {
+    let func = |input: &str| parse.parse(input, params);
+    match catch_panic(|| run_timed(func, input, |_| {}, bench, false)) {
+        Ok((parsed, duration, samples, stats)) => {
+            let mut reports = vec![PartReport::new::<String>(
+                PARSE_PART, None, &duration, samples, stats,
+            )];
+            reports.extend(run_parts(&parsed));
+            reports
+        }
+        Err(error) => iter::once(PartReport::failed(PARSE_PART, error))
+            .chain(
+                parts
+                    .iter()
+                    .map(|part| PartReport::failed(*part, PARSE_FAILED.into())),
+            )
+            .collect(),
+    }
+}
+
+thread_local! {
+    /// Whether panics on this thread are caught by [`catch_panic`].
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// Message and location of the last panic caught on this thread.
+    static PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `func` and returns the message and location of the panic if it panics.
+/// While a part runs, the default panic output is replaced by the returned message.
+fn catch_panic<R>(func: impl FnOnce() -> R) -> Result<R, String> {
+    static HOOK: Once = Once::new();
+
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() {
+                PANIC.set(Some(format_panic(info)));
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+
+    let was_catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    CATCHING.set(was_catching);
+
+    result.map_err(|_| {
+        PANIC
+            .take()
+            .unwrap_or_else(|| "panicked at an unknown location".into())
+    })
+}
+
+fn format_panic(info: &PanicHookInfo) -> String {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>");
+
+    match info.location() {
+        Some(location) => format!("panicked at {location}: {message}"),
+        None => format!("panicked: {message}"),
+    }
+}
+
+/// A solution that is linked into the runner binary. Created by the [`solution!`](crate::solution) macro.
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub year: Year,
+    pub day: Day,
+    /// Runs all parts of the solution on an input with its parameters and reports their results.
+    pub run: fn(&str, &Params, Option<&BenchConfig>) -> Vec<PartReport>,
+}
+
+/// Run a solution part. The behavior differs depending on whether a `bench` configuration is passed:
+///  1. without, the function is executed once.
+///  2. with, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    bench_config: Option<&BenchConfig>,
+    show_progress: bool,
+) -> (T, Duration, u128, Option<BenchStats>) {
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -41,23 +580,268 @@ fn run_timed<I: Copy, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if let Some(config) = bench_config {
+        let (samples, stats) = bench(func, input, &base_time, config, show_progress);
+        (result, stats.mean_duration(), samples, Some(stats))
     } else {
-        (base_time, 1)
+        (result, base_time, 1, None)
+    }
+}
+
+/// Configuration of a benchmark, read from the `--iterations <n>` and `--budget <duration>` arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// A fixed number of iterations. If not set, the number of iterations is derived from `budget`.
+    pub iterations: Option<u128>,
+    /// Approximate time to spend benching a part.
+    pub budget: Duration,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            iterations: None,
+            budget: Duration::from_secs(1),
+        }
+    }
+}
+
+impl BenchConfig {
+    const MIN_ITERATIONS: u128 = 10;
+    const MAX_ITERATIONS: u128 = 10_000;
+
+    fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|index| args.get(index + 1))
+        };
+
+        let mut config = Self::default();
+
+        if let Some(iterations) = value_of("--iterations") {
+            let Ok(iterations) = iterations.parse::<u128>() else {
+                eprintln!("Unexpected command-line input. Format: --iterations <n>");
+                process::exit(1);
+            };
+            if iterations == 0 {
+                eprintln!("expecting at least 1 iteration.");
+                process::exit(1);
+            }
+            config.iterations = Some(iterations);
+        }
+
+        if let Some(budget) = value_of("--budget") {
+            config.budget = parse_duration(budget).unwrap_or_else(|e| {
+                eprintln!("{e}");
+                process::exit(1);
+            });
+        }
+
+        config
+    }
+
+    /// Arguments that reproduce this configuration in a child invocation.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if let Some(iterations) = self.iterations {
+            args.extend(["--iterations".into(), iterations.to_string()]);
+        }
+        if self.budget != Self::default().budget {
+            args.extend(["--budget".into(), format!("{}ns", self.budget.as_nanos())]);
+        }
+        args
+    }
+
+    /// Number of timed iterations, either fixed or based on how long the first execution took.
+    fn iterations(&self, base_time: &Duration) -> u128 {
+        self.iterations.unwrap_or_else(|| {
+            (self.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+                .clamp(Self::MIN_ITERATIONS, Self::MAX_ITERATIONS)
+        })
+    }
+}
+
+/// Parses a duration like `500ms`, `1.5s` or `2m`.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("invalid duration \"{s}\", expecting e.g. 500ms or 2s."))?;
+
+    let factor = match unit.trim() {
+        "ns" => 1e-9,
+        "us" | "µs" => 1e-6,
+        "ms" => 1e-3,
+        "s" => 1.0,
+        "m" => 60.0,
+        _ => {
+            return Err(format!(
+                "invalid duration unit in \"{s}\", expecting one of ns, µs, ms, s, m."
+            ));
+        }
     };
 
-    (result, run.0, run.1)
+    Duration::try_from_secs_f64(value * factor)
+        .map_err(|_| format!("invalid duration \"{s}\", the value is too large."))
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Resource limits of a solution, read from the `--timeout <duration>` and `--memory <size>` arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Limits {
+    /// Maximum execution time of a single part.
+    pub timeout: Option<Duration>,
+    /// Maximum memory of the solution process in bytes. Only enforced on unix.
+    pub memory: Option<u64>,
+}
+
+impl Limits {
+    fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|index| args.get(index + 1))
+        };
+
+        let exit = |e: String| -> ! {
+            eprintln!("{e}");
+            process::exit(1);
+        };
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+        Self {
+            timeout: value_of("--timeout").map(|x| parse_duration(x).unwrap_or_else(|e| exit(e))),
+            memory: value_of("--memory").map(|x| parse_memory(x).unwrap_or_else(|e| exit(e))),
+        }
+    }
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    /// Whether any limit is set.
+    pub fn is_set(&self) -> bool {
+        self.timeout.is_some() || self.memory.is_some()
+    }
+
+    /// Arguments that reproduce these limits in a child invocation.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if let Some(timeout) = self.timeout {
+            args.extend(["--timeout".into(), format!("{}ns", timeout.as_nanos())]);
+        }
+        if let Some(memory) = self.memory {
+            args.extend(["--memory".into(), format_memory(memory)]);
+        }
+        args
+    }
+}
+
+/// Parses a memory size like `512M` or `2G`. Units are binary, i.e. `1K` is 1024 bytes.
+pub fn parse_memory(s: &str) -> Result<u64, String> {
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+
+    let value: u64 = value
+        .parse()
+        .map_err(|_| format!("invalid memory size \"{s}\", expecting e.g. 512M or 2G."))?;
+
+    let factor: u64 = match unit.trim() {
+        "" | "B" => 1,
+        "K" | "KB" | "KiB" => 1 << 10,
+        "M" | "MB" | "MiB" => 1 << 20,
+        "G" | "GB" | "GiB" => 1 << 30,
+        _ => {
+            return Err(format!(
+                "invalid memory unit in \"{s}\", expecting one of B, K, M, G."
+            ));
+        }
+    };
+
+    value
+        .checked_mul(factor)
+        .ok_or(format!("memory size \"{s}\" is too large."))
+}
+
+/// Formats a memory size in the largest unit that represents it exactly.
+pub fn format_memory(bytes: u64) -> String {
+    match bytes {
+        0 => "0B".into(),
+        x if x % (1 << 30) == 0 => format!("{}G", x >> 30),
+        x if x % (1 << 20) == 0 => format!("{}M", x >> 20),
+        x if x % (1 << 10) == 0 => format!("{}K", x >> 10),
+        x => format!("{x}B"),
+    }
+}
+
+/// Limits the address space of the process. Allocations beyond the limit abort the process.
+#[cfg(unix)]
+fn limit_memory(bytes: u64) {
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+
+    // SAFETY: `limit` is a valid, writable `rlimit` for the duration of both calls.
+    let result = unsafe {
+        if libc::getrlimit(libc::RLIMIT_AS, &raw mut limit) == 0 {
+            limit.rlim_cur = (bytes as libc::rlim_t).min(limit.rlim_max);
+            libc::setrlimit(libc::RLIMIT_AS, &raw const limit)
+        } else {
+            -1
+        }
+    };
+
+    if result != 0 {
+        eprintln!("failed to limit memory: {}", io::Error::last_os_error());
+    }
+}
+
+#[cfg(not(unix))]
+fn limit_memory(_bytes: u64) {
+    eprintln!("Memory limits are only supported on unix, ignoring --memory.");
+}
+
+/// Calls `on_timeout` on a separate thread if it is not dropped within `timeout`.
+struct Watchdog {
+    _stop: Sender<()>,
+}
+
+impl Watchdog {
+    fn start(timeout: Duration, on_timeout: impl FnOnce() + Send + 'static) -> Self {
+        let (sender, receiver) = mpsc::channel::<()>();
+
+        thread::spawn(move || {
+            // NOTE: dropping the watchdog disconnects the channel and ends the wait early.
+            if receiver.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout) {
+                on_timeout();
+            }
+        });
+
+        Self { _stop: sender }
+    }
+}
+
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+    show_progress: bool,
+) -> (u128, BenchStats) {
+    if show_progress {
+        let mut stdout = stdout();
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
+
+    let bench_iterations = config.iterations(base_time);
+
+    // warm up caches and branch predictors before measuring.
+    for _ in 0..cmp::max(bench_iterations / 10, 1) {
+        black_box(func(black_box(input)));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -67,22 +851,114 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    (bench_iterations, BenchStats::from_samples(&timers))
+}
+
+/// Summary statistics of a benchmark in nanoseconds.
+/// Outliers are detected with the median absolute deviation (MAD) and excluded from all other values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchStats {
+    pub mean: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub std_dev: f64,
+    pub p95: f64,
+    /// Total number of samples, including outliers.
+    pub samples: usize,
+    pub outliers: usize,
+}
+
+impl BenchStats {
+    /// Modified z-score above which a sample counts as an outlier, see Iglewicz and Hoaglin.
+    const OUTLIER_THRESHOLD: f64 = 3.5;
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut nanos: Vec<f64> = samples.iter().map(|x| x.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let center = median(&nanos);
+
+        let mut deviations: Vec<f64> = nanos.iter().map(|x| (x - center).abs()).collect();
+        deviations.sort_unstable_by(f64::total_cmp);
+        let mad = median(&deviations);
+
+        let inliers: Vec<f64> = if mad > 0.0 {
+            nanos
+                .iter()
+                .copied()
+                .filter(|x| 0.6745 * (x - center).abs() / mad <= Self::OUTLIER_THRESHOLD)
+                .collect()
+        } else {
+            nanos.clone()
+        };
+
+        let n = inliers.len() as f64;
+        let mean = inliers.iter().sum::<f64>() / n;
+        let variance = if inliers.len() > 1 {
+            inliers.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        Self {
+            mean,
+            median: median(&inliers),
+            min: inliers.first().copied().unwrap_or_default(),
+            max: inliers.last().copied().unwrap_or_default(),
+            std_dev: variance.sqrt(),
+            p95: percentile(&inliers, 0.95),
+            samples: nanos.len(),
+            outliers: nanos.len() - inliers.len(),
+        }
+    }
+
+    pub fn mean_duration(&self) -> Duration {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(self.mean as u64)
+    }
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Median of a sorted slice.
+fn median(sorted: &[f64]) -> f64 {
+    match sorted.len() {
+        0 => 0.0,
+        n if n % 2 == 0 => f64::midpoint(sorted[n / 2 - 1], sorted[n / 2]),
+        n => sorted[n / 2],
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
+/// Nearest-rank percentile of a sorted slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+pub(crate) fn print_stats(stats: &BenchStats) {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let fmt = |nanos: f64| format!("{:.1?}", Duration::from_nanos(nanos as u64));
+
+    println!(
+        "  {ANSI_ITALIC}median {} · min {} · max {} · σ {} · p95 {} · {} outliers{ANSI_RESET}",
+        fmt(stats.median),
+        fmt(stats.min),
+        fmt(stats.max),
+        fmt(stats.std_dev),
+        fmt(stats.p95),
+        stats.outliers
+    );
+}
+
+pub(crate) fn format_duration(duration: &Duration, samples: u128) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
     } else {
@@ -90,7 +966,42 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_parse(duration_str: &str) {
+    if duration_str.is_empty() {
+        print!("Parse:");
+    } else {
+        print!("\r");
+        println!("Parse:{duration_str}             ");
+    }
+}
+
+/// Prints the report of a part or the parse step like the runner of a solution does.
+pub(crate) fn print_report(report: &PartReport) {
+    let label = part_label(report.part);
+    let duration = format_duration(&report.duration(), report.samples);
+
+    if let Some(error) = &report.error {
+        print_error(&label, error);
+        return;
+    }
+
+    if report.part == PARSE_PART {
+        print_parse(&duration);
+    } else {
+        print_result(&report.result, &label, &duration);
+    }
+
+    if let Some(stats) = &report.stats {
+        print_stats(stats);
+    }
+}
+
+pub(crate) fn print_error(part: &str, error: &str) {
+    print!("\r");
+    println!("{part}: ✖ {ANSI_ITALIC}{error}{ANSI_RESET}");
+}
+
+pub(crate) fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
@@ -127,12 +1038,14 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+///  2. a session cookie is configured.
+///  3. the letters of a drawn result can be read.
+///  4. the answer ledger does not rule out the result.
+fn submit_result(
+    result: &Answer,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<SubmissionOutcome, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -155,13 +1068,328 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+    let Some(result) = result.canonical() else {
+        eprintln!("Not submitting: the letters of the result could not be read.");
+        return None;
+    };
+
+    let mut answers = match Answers::read_from_file() {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!(
+                "Not submitting {result}: the answer ledger could not be read. Fix or remove it first: {e}"
+            );
+            return None;
+        }
+    };
+
+    if let Err(refusal) = answers.check(day, part, &result) {
+        eprintln!("Not submitting {result}: {refusal}");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let outcome = aoc_client::submit(day, part, &result);
+
+    if let Ok(outcome) = &outcome {
+        answers.record(day, part, &result, outcome);
+        if let Err(e) = answers.store_file() {
+            eprintln!("failed to store answers: {e}");
+        }
+    }
+
+    Some(outcome)
+}
+
+fn print_submission(outcome: Result<SubmissionOutcome, AocClientError>) {
+    match outcome {
+        Ok(SubmissionOutcome::Correct) => println!("⭐ That's the right answer!"),
+        Ok(SubmissionOutcome::Incorrect(hint)) => {
+            let hint = match hint {
+                Some(Hint::TooHigh) => " Your answer is too high.",
+                Some(Hint::TooLow) => " Your answer is too low.",
+                None => "",
+            };
+            println!("✖ That's not the right answer.{hint}");
+        }
+        Ok(SubmissionOutcome::TooRecent(wait)) => println!(
+            "⏳ You gave an answer too recently. Time left to wait: {}.",
+            wait.as_deref().unwrap_or("unknown")
+        ),
+        Ok(SubmissionOutcome::WrongLevel) => {
+            println!("This part is either already solved or not unlocked yet.");
+        }
+        Ok(SubmissionOutcome::Unknown(message)) => println!("{message}"),
+        Err(e) => {
+            eprintln!("failed to submit result: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "result".into(),
+            value
+                .result
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
         );
-        process::exit(1);
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "error".into(),
+            value
+                .error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl FromStr for PartReport {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected report.{key} to be a number."))
+        };
+
+        let optional_string = |key: &str| match json.get(key) {
+            Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+            _ => Err(format!("Expected report.{key} to be null or string.")),
+        };
+
+        let stats = match json.get("stats") {
+            None | Some(JsonValue::Null) => None,
+            Some(stats) => Some(BenchStats::try_from(stats)?),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(PartReport {
+            part: number("part")? as u8,
+            result: optional_string("result")?,
+            nanos: number("nanos")?,
+            samples: number("samples")? as u128,
+            stats,
+            error: optional_string("error")?,
+        })
+    }
+}
+
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("max".into(), JsonValue::Number(value.max));
+        map.insert("std_dev".into(), JsonValue::Number(value.std_dev));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(BenchStats {
+            mean: number("mean")?,
+            median: number("median")?,
+            min: number("min")?,
+            max: number("max")?,
+            std_dev: number("std_dev")?,
+            p95: number("p95")?,
+            samples: number("samples")? as usize,
+            outliers: number("outliers")? as usize,
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        BenchConfig, BenchStats, Limits, PartReport, execute_part, format_memory, parse_duration,
+        parse_memory,
+    };
+    use crate::template::Params;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_part_reports() {
+        let report = PartReport {
+            part: 2,
+            result: Some("a \"quoted\"\nresult".into()),
+            nanos: 74.13,
+            samples: 100_000,
+            stats: Some(BenchStats::from_samples(&[
+                Duration::from_nanos(70),
+                Duration::from_nanos(80),
+            ])),
+            error: None,
+        };
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert!(!line.contains('\n'));
+        assert_eq!(line.parse::<PartReport>().unwrap(), report);
+    }
+
+    #[test]
+    fn reports_panicking_parts() {
+        let params = Params::default();
+        let report = execute_part(
+            |_: &str| -> Option<u32> { panic!("boom") },
+            "",
+            &params,
+            1,
+            None,
+        );
+        assert_eq!(report.result, None);
+        let error = report.error.unwrap();
+        assert!(error.starts_with("panicked at src/template/runner.rs:"));
+        assert!(error.ends_with(": boom"));
+
+        let report = execute_part(
+            |input: &str| input.parse::<u32>().ok(),
+            "42",
+            &params,
+            2,
+            None,
+        );
+        assert_eq!(report.result.as_deref(), Some("42"));
+        assert_eq!(report.error, None);
+
+        let params = Params::from([("offset", "2")]);
+        let part = |input: &str, params: &Params| {
+            Some(input.parse::<u32>().ok()? + params.get_or("offset", 0))
+        };
+        let report = execute_part(part, "42", &params, 2, None);
+        assert_eq!(report.result.as_deref(), Some("44"));
+    }
+
+    #[test]
+    fn rejects_malformed_reports() {
+        assert!("Part 1: 42 (1.0ms)".parse::<PartReport>().is_err());
+        assert!(r#"{ "part": 1 }"#.parse::<PartReport>().is_err());
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn computes_bench_stats() {
+        let samples: Vec<Duration> = [5, 1, 4, 2, 3].map(Duration::from_nanos).to_vec();
+        let stats = BenchStats::from_samples(&samples);
+        assert_eq!(stats.mean, 3.0);
+        assert_eq!(stats.median, 3.0);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 5.0);
+        assert_eq!(stats.p95, 5.0);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+        assert!((stats.std_dev - 2.5_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn excludes_outliers() {
+        let mut samples: Vec<Duration> = (0..20).map(|x| Duration::from_nanos(100 + x)).collect();
+        samples.push(Duration::from_nanos(10_000));
+        let stats = BenchStats::from_samples(&samples);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max, 119.0);
+        assert_eq!(stats.median, 109.5);
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_duration("10µs"), Ok(Duration::from_micros(10)));
+        assert!(parse_duration("10").is_err());
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("99999999999999999999999m").is_err());
+    }
+
+    #[test]
+    fn parses_memory_sizes() {
+        assert_eq!(parse_memory("512M"), Ok(512 << 20));
+        assert_eq!(parse_memory("2GiB"), Ok(2 << 30));
+        assert_eq!(parse_memory("100"), Ok(100));
+        assert!(parse_memory("1T").is_err());
+        assert!(parse_memory("lots").is_err());
+
+        assert_eq!(format_memory(512 << 20), "512M");
+        assert_eq!(format_memory(1536), "1536B");
+    }
+
+    #[test]
+    fn derives_limit_args() {
+        assert!(Limits::default().to_args().is_empty());
+
+        let limits = Limits {
+            timeout: Some(Duration::from_secs(5)),
+            memory: Some(1 << 30),
+        };
+        assert!(limits.is_set());
+        assert_eq!(
+            limits.to_args(),
+            ["--timeout", "5000000000ns", "--memory", "1G"]
+        );
+    }
+
+    #[test]
+    fn derives_bench_iterations() {
+        let config = BenchConfig::default();
+        assert_eq!(config.iterations(&Duration::from_millis(1)), 1000);
+        assert_eq!(config.iterations(&Duration::from_secs(1)), 10);
+        assert_eq!(config.iterations(&Duration::from_nanos(1)), 10_000);
+
+        let config = BenchConfig {
+            iterations: Some(42),
+            budget: Duration::from_secs(1),
+        };
+        assert_eq!(config.iterations(&Duration::from_secs(1)), 42);
+        assert_eq!(config.to_args(), ["--iterations", "42"]);
+    }
 }
@@ -1,16 +1,68 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::runner::BenchStats;
+use crate::template::{Day, Year};
+
+/// Version of the `timings.json` schema written by [`Timings::store_file`].
+///  1. parts stored as display strings, e.g. `"74.13ns"`. Files of this version do not have a `version` key.
+///  2. parts stored as objects with numeric nanoseconds, sample count and measurement time.
+const TIMINGS_VERSION: u8 = 2;
+
+/// Represents the benchmark time of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    pub nanos: f64,
+    /// Number of benchmark samples. `None` for timings migrated from version 1.
+    pub samples: Option<u128>,
+    /// Unix timestamp in seconds. `None` for timings migrated from version 1.
+    pub measured_at: Option<u64>,
+    pub stats: Option<BenchStats>,
+}
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+impl PartTiming {
+    /// Creates a timing measured right now.
+    pub fn new(nanos: f64, samples: u128, stats: Option<BenchStats>) -> Self {
+        let measured_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.as_secs())
+            .ok();
+
+        Self {
+            nanos,
+            samples: Some(samples),
+            measured_at,
+            stats,
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(self.nanos as u64)
+    }
+}
+
+impl std::fmt::Display for PartTiming {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.1?}", self.duration())
+    }
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// Time to parse the input, for solutions with a `parse` function.
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
 }
 
@@ -22,16 +74,22 @@ pub struct Timings {
 }
 
 impl Timings {
+    /// Path of the current year's timings file.
+    pub fn file_path() -> PathBuf {
+        Year::current().data_dir().join("timings.json")
+    }
+
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        fs::create_dir_all(Year::current().data_dir())?;
+        let mut file = fs::File::create(Self::file_path())?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+        fs::read_to_string(Self::file_path())
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
@@ -51,7 +109,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -73,6 +131,10 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(TIMINGS_VERSION)),
+        );
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -88,19 +150,33 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        // NOTE: version 1 files do not carry a version.
+        let version = match json.get("version") {
+            None => 1,
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Some(v) => *v
+                .get::<f64>()
+                .ok_or("expected `json.version` to be a number.")? as u8,
+        };
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        let parse: fn(&JsonValue) -> Result<Timing, String> = match version {
+            1 => migrate_v1,
+            TIMINGS_VERSION => |v| Timing::try_from(v),
+            _ => return Err(format!("unsupported timings version {version}.")),
+        };
+
         Ok(Timings {
-            data: json_data
-                .iter()
-                .map(Timing::try_from)
-                .collect::<Result<_, _>>()?,
+            data: json_data.iter().map(parse).collect::<Result<_, _>>()?,
         })
     }
 }
@@ -114,24 +190,12 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        let part =
+            |part: &Option<PartTiming>| part.as_ref().map_or(JsonValue::Null, JsonValue::from);
 
-        map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
-
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        map.insert("parse".into(), part(&value.parse));
+        map.insert("part_1".into(), part(&value.part_1));
+        map.insert("part_2".into(), part(&value.part_2));
 
         JsonValue::Object(map)
     }
@@ -145,62 +209,194 @@ impl TryFrom<&JsonValue> for Timing {
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
-        let day = json
-            .get("day")
-            .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
-            .ok_or("Expected timing.day to be a Day struct.")?;
-
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        let day = parse_day(json)?;
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        let part = |key: &str| match json.get(key) {
+            Some(JsonValue::Null) => Ok(None),
+            Some(part) => PartTiming::try_from(part).map(Some),
+            None => Err(format!("Expected timing.{key} to be null or object.")),
+        };
 
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: timings stored before parse steps were timed do not have a `parse` key.
+        let parse = match json.get("parse") {
+            None => None,
+            Some(_) => part("parse")?,
+        };
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
             total_nanos,
         })
     }
 }
 
+/// Reads a timing stored in the version 1 layout, where parts are display strings and stats live in separate keys.
+fn migrate_v1(value: &JsonValue) -> Result<Timing, String> {
+    let json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected timing to be a JSON object.")?;
+
+    let day = parse_day(json)?;
+
+    let part = |key: &str| -> Result<Option<PartTiming>, String> {
+        let timing_str = json
+            .get(key)
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or(format!("Expected timing.{key} to be null or string."))?;
+
+        let Some(timing_str) = timing_str else {
+            return Ok(None);
+        };
+
+        let nanos = parse_display_duration(timing_str)
+            .ok_or(format!("Could not parse timing.{key} \"{timing_str}\"."))?;
+
+        let stats = match json.get(&format!("{key}_stats")) {
+            None | Some(JsonValue::Null) => None,
+            Some(stats) => Some(BenchStats::try_from(stats)?),
+        };
+
+        Ok(Some(PartTiming {
+            nanos,
+            samples: stats.map(|s| s.samples as u128),
+            measured_at: None,
+            stats,
+        }))
+    };
+
+    let total_nanos = json
+        .get("total_nanos")
+        .and_then(|v| v.get::<f64>().copied())
+        .ok_or("Expected timing.total_nanos to be a number.")?;
+
+    Ok(Timing {
+        day,
+        parse: None,
+        part_1: part("part_1")?,
+        part_2: part("part_2")?,
+        total_nanos,
+    })
+}
+
+fn parse_day(json: &HashMap<String, JsonValue>) -> Result<Day, String> {
+    Ok(json
+        .get("day")
+        .and_then(|v| v.get::<String>())
+        .and_then(|day| Day::from_str(day).ok())
+        .ok_or("Expected timing.day to be a Day struct.")?)
+}
+
+/// Parses a duration formatted with `{:?}` back to nanoseconds.
+fn parse_display_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let parse = |postfix: &str| s.split(postfix).next()?.parse::<f64>().ok();
+
+    match s {
+        s if s.ends_with("ns") => parse("ns"),
+        s if s.ends_with("µs") => parse("µs").map(|x| x * 1000_f64),
+        s if s.ends_with("ms") => parse("ms").map(|x| x * 1_000_000_f64),
+        _ => parse("s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        let number = |x: Option<f64>| x.map_or(JsonValue::Null, JsonValue::Number);
+
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), number(value.samples.map(|x| x as f64)));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "measured_at".into(),
+            number(value.measured_at.map(|x| x as f64)),
+        );
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let optional_number = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::Number(x)) => Ok(Some(*x)),
+            Some(_) => Err(format!("Expected part timing.{key} to be null or number.")),
+        };
+
+        let nanos =
+            optional_number("nanos")?.ok_or("Expected part timing.nanos to be a number.")?;
+
+        let stats = match json.get("stats") {
+            None | Some(JsonValue::Null) => None,
+            Some(stats) => Some(BenchStats::try_from(stats)?),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(PartTiming {
+            nanos,
+            samples: optional_number("samples")?.map(|x| x as u128),
+            measured_at: optional_number("measured_at")?.map(|x| x as u64),
+            stats,
+        })
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parse: None,
+                    part_1: Some(PartTiming::new(10_000_000.0, 1, None)),
+                    part_2: Some(PartTiming::new(20_000_000.0, 1, None)),
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse: Some(PartTiming::new(5_000_000.0, 1, None)),
+                    part_1: Some(PartTiming::new(30_000_000.0, 1, None)),
+                    part_2: Some(PartTiming::new(40_000_000.0, 1, None)),
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(PartTiming::new(40_000_000.0, 1, None)),
                     part_2: None,
                     total_nanos: 4e+10,
                 },
@@ -213,16 +409,49 @@ mod tests {
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 1000000, "samples": 100, "measured_at": 1733000000, "stats": null }, "part_2": null, "total_nanos": 1000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!(part_1.nanos, 1_000_000_f64);
+            assert_eq!(part_1.samples, Some(100));
+            assert_eq!(part_1.measured_at, Some(1_733_000_000));
+            assert_eq!(part_1.to_string(), "1.0ms");
             assert_eq!(timing.part_2, None);
+            assert_eq!(timing.total_nanos, 1_000_000_f64);
+        }
+
+        #[test]
+        fn migrates_v1_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "74.13ns", "total_nanos": 1000000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data.len(), 1);
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.day, day!(1));
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!(part_1.nanos, 1_000_000_f64);
+            assert_eq!(part_1.samples, None);
+            assert_eq!(part_1.measured_at, None);
+            assert_eq!(timing.part_2.as_ref().unwrap().nanos, 74.13);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn migrates_v1_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.5µs", "part_2": null, "total_nanos": 1000000000, "part_1_stats": { "mean": 1, "median": 2, "min": 0, "max": 3, "std_dev": 0.5, "p95": 3, "samples": 10, "outliers": 4 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!(part_1.nanos, 1500.0);
+            assert_eq!(part_1.samples, Some(10));
+            let stats = part_1.stats.unwrap();
+            assert_eq!(stats.median, 2.0);
+            assert_eq!(stats.outliers, 4);
+            assert_eq!(timing.part_2, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -243,6 +472,13 @@ mod tests {
             let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_unknown_versions() {
+            let json = r#"{ "version": 99, "data": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
     }
 
     mod serialization {
@@ -266,12 +502,24 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn roundtrips_timings() {
+            let timings = get_mock_timings();
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = super::Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data.len(), 3);
+            assert_eq!(parsed.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(parsed.data[2].part_2, None);
+            assert_eq!(parsed.data[0].parse, None);
+            assert_eq!(parsed.data[1].parse, timings.data[1].parse);
+        }
     }
 
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{PartTiming, Timing, Timings},
         };
 
         #[test]
@@ -279,13 +527,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    parse: None,
+                    part_1: Some(PartTiming::new(1_000_000.0, 1, None)),
+                    part_2: Some(PartTiming::new(2_000_000.0, 1, None)),
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +542,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    parse: None,
+                    part_1: Some(PartTiming::new(1_000_000.0, 1, None)),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,13 +557,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -331,6 +582,7 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
@@ -351,6 +603,7 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
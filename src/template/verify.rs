@@ -0,0 +1,317 @@
+/// Regression checks of solutions against stored answers for the real inputs.
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::answers::Answers;
+use crate::template::examples::scalar;
+use crate::template::run_multi::{child_commands, get_path_for_bin};
+use crate::template::runner::{InputSource, Limits, PartReport};
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, Error, Year, all_days};
+
+/// Outcome of verifying a single part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// No expected answer is stored for this part.
+    Missing,
+    /// The part panicked or exceeded a limit, or the solution could not run, e.g. without an input.
+    Error {
+        error: String,
+    },
+}
+
+impl Status {
+    pub fn new(expected: Option<&str>, actual: Option<&str>) -> Self {
+        match (expected, actual) {
+            (None, _) => Status::Missing,
+            (Some(expected), Some(actual)) if expected == actual => Status::Pass,
+            (Some(expected), actual) => Status::Fail {
+                expected: expected.to_string(),
+                actual: actual.map(str::to_string),
+            },
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Fail { .. } | Status::Error { .. })
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => write!(f, "✔ pass"),
+            Status::Fail { expected, actual } => write!(
+                f,
+                "✖ fail (expected {expected}, got {})",
+                actual.as_deref().unwrap_or("nothing")
+            ),
+            Status::Missing => write!(f, "- missing"),
+            Status::Error { error } => write!(f, "✖ {error}"),
+        }
+    }
+}
+
+/// Verification results for a single day and input.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub day: Day,
+    /// Name of the input, `None` for the puzzle input of the day.
+    pub input: Option<String>,
+    pub parts: [Status; 2],
+}
+
+/// A named input of a day, e.g. a teammate's input in `data/{year}/inputs/{day}/alice.txt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedInput {
+    pub name: String,
+    pub path: PathBuf,
+    /// Known answers of part 1 and 2, read from `answers.json` next to the inputs.
+    pub answers: [Option<String>; 2],
+}
+
+/// Lists the named inputs of a day, sorted by name. Days without an input directory have none.
+pub fn named_inputs(year: Year, day: Day) -> Result<Vec<NamedInput>, Error> {
+    let dir = year.data_dir().join("inputs").join(day.to_string());
+
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(Error::io(dir)(e)),
+    };
+
+    let answers_path = dir.join("answers.json");
+    let answers = match fs::read_to_string(&answers_path) {
+        Ok(content) => parse_answers(&content)
+            .map_err(|e| Error::io(&answers_path)(io::Error::new(io::ErrorKind::InvalidData, e)))?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => HashMap::new(),
+        Err(e) => return Err(Error::io(answers_path)(e)),
+    };
+
+    let mut inputs = entries
+        .map(|entry| entry.map(|entry| entry.path()).map_err(Error::io(&dir)))
+        .filter(|path| {
+            path.as_ref()
+                .map_or(true, |path| path.extension().is_some_and(|x| x == "txt"))
+        })
+        .map(|path| {
+            let path = path?;
+            let name = path
+                .file_stem()
+                .map(|x| x.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let answers = answers.get(&name).cloned().unwrap_or_default();
+            Ok(NamedInput {
+                name,
+                path,
+                answers,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    inputs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(inputs)
+}
+
+/// Parses the answers of named inputs, e.g. `{ "alice": { "part_1": 42, "part_2": "abc" } }`.
+fn parse_answers(s: &str) -> Result<HashMap<String, [Option<String>; 2]>, String> {
+    let json = JsonValue::from_str(s).or(Err("not valid JSON."))?;
+    let json = json
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected answers to be a JSON object.")?;
+
+    json.iter()
+        .map(|(name, value)| {
+            let parts = value
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or(format!("Expected answers.{name} to be a JSON object."))?;
+
+            let answer = |key: &str| match parts.get(key) {
+                None | Some(JsonValue::Null) => Ok(None),
+                Some(value) => scalar(value).map(Some).ok_or(format!(
+                    "Expected answers.{name}.{key} to be a string or number."
+                )),
+            };
+
+            Ok((name.clone(), [answer("part_1")?, answer("part_2")?]))
+        })
+        .collect()
+}
+
+/// Compares the reports of a solution to the expected answers of both parts.
+/// Both parts are errors if the solution could not run. The solution already printed why its input is missing.
+fn statuses(reports: &Result<Vec<PartReport>, Error>, expected: [Option<&str>; 2]) -> [Status; 2] {
+    let reports = match reports {
+        Ok(reports) => reports,
+        Err(e) => {
+            let error = match e {
+                Error::MissingInput { .. } => "no input".to_string(),
+                e => e.to_string(),
+            };
+            return [error.clone(), error].map(|error| Status::Error { error });
+        }
+    };
+
+    let status = |part: u8| {
+        let report = reports.iter().find(|report| report.part == part);
+
+        if let Some(error) = report.and_then(|report| report.error.clone()) {
+            return Status::Error { error };
+        }
+
+        Status::new(
+            expected[usize::from(part) - 1],
+            report.and_then(|report| report.result.as_deref()),
+        )
+    };
+
+    [status(1), status(2)]
+}
+
+/// Runs the solutions for `days_to_verify` and compares their results to the accepted answers in the ledger.
+/// With `all_inputs`, the solutions also run on the named inputs of each day, see [`named_inputs`].
+/// Days that have not been scaffolded yet are skipped.
+pub fn verify(
+    days_to_verify: &HashSet<Day>,
+    is_release: bool,
+    limits: &Limits,
+    all_inputs: bool,
+) -> Result<Vec<Report>, Error> {
+    let answers = Answers::read_from_file()?;
+    let mut reports = vec![];
+
+    for day in all_days()
+        .filter(|day| days_to_verify.contains(day))
+        .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+    {
+        println!("Verifying day {day}...");
+
+        let parts = child_commands::run_solution(day, None, is_release, limits);
+        let accepted = |part: u8| {
+            answers
+                .get(day, part)
+                .and_then(|answers| answers.accepted.as_deref())
+        };
+
+        reports.push(Report {
+            day,
+            input: None,
+            parts: statuses(&parts, [accepted(1), accepted(2)]),
+        });
+
+        if !all_inputs {
+            continue;
+        }
+
+        for input in named_inputs(Year::current(), day)? {
+            println!("Verifying day {day} ({})...", input.name);
+
+            let source = InputSource::File(input.path.clone());
+            let parts = child_commands::run_solution_on(day, &source, is_release, limits);
+
+            reports.push(Report {
+                day,
+                parts: statuses(&parts, [0, 1].map(|i| input.answers[i].as_deref())),
+                input: Some(input.name),
+            });
+        }
+    }
+
+    Ok(reports)
+}
+
+pub fn print_matrix(reports: &[Report]) {
+    println!();
+    println!("{ANSI_BOLD}Day | Part 1 | Part 2{ANSI_RESET}");
+
+    for report in reports {
+        let day = match &report.input {
+            Some(input) => format!("{} ({input})", report.day),
+            None => report.day.to_string(),
+        };
+        println!("{day} | {} | {}", report.parts[0], report.parts[1]);
+    }
+
+    let count = |f: fn(&Status) -> bool| {
+        reports
+            .iter()
+            .flat_map(|report| report.parts.iter())
+            .filter(|status| f(status))
+            .count()
+    };
+
+    println!();
+    println!(
+        "{ANSI_BOLD}Passed:{ANSI_RESET} {}, {ANSI_BOLD}Failed:{ANSI_RESET} {}, {ANSI_BOLD}Missing:{ANSI_RESET} {}",
+        count(|s| *s == Status::Pass),
+        count(Status::is_failure),
+        count(|s| *s == Status::Missing),
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Status, parse_answers, statuses};
+    use crate::template::{Day, Error, Year};
+
+    #[test]
+    fn compares_results() {
+        assert_eq!(Status::new(Some("42"), Some("42")), Status::Pass);
+        assert_eq!(Status::new(None, Some("42")), Status::Missing);
+        assert_eq!(Status::new(None, None), Status::Missing);
+        assert_eq!(
+            Status::new(Some("42"), Some("43")),
+            Status::Fail {
+                expected: "42".into(),
+                actual: Some("43".into())
+            }
+        );
+        assert!(Status::new(Some("42"), None).is_failure());
+        assert!(
+            Status::Error {
+                error: "panicked at src/bin/2025-01.rs:4:5: boom".into()
+            }
+            .is_failure()
+        );
+    }
+
+    #[test]
+    fn reports_solutions_that_cannot_run() {
+        let missing = || Error::MissingInput {
+            year: Year::new(2025).unwrap(),
+            day: Day::new(1).unwrap(),
+            path: "data/2025/inputs/01.txt".into(),
+            is_empty: false,
+        };
+        let error = Status::Error {
+            error: "no input".into(),
+        };
+        assert_eq!(
+            statuses(&Err(missing()), [Some("42"), None]),
+            [error.clone(), error]
+        );
+    }
+
+    #[test]
+    fn parses_answers_of_named_inputs() {
+        let answers =
+            parse_answers(r#"{ "alice": { "part_1": 42, "part_2": "abc" }, "bob": {} }"#).unwrap();
+        assert_eq!(answers["alice"], [Some("42".into()), Some("abc".into())]);
+        assert_eq!(answers["bob"], [None, None]);
+
+        assert!(parse_answers(r#"{ "alice": 42 }"#).is_err());
+        assert!(parse_answers(r#"{ "alice": { "part_1": [] } }"#).is_err());
+    }
+}
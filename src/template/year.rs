@@ -0,0 +1,211 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::template::{AllDays, Day};
+
+/// Year of the latest event. Used if no year is configured.
+const LATEST_YEAR: u16 = 2025;
+
+static CURRENT_YEAR: OnceLock<Year> = OnceLock::new();
+
+/// A valid year of advent of code (i.e. 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.to_string(), "2024")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a year of advent of code,
+    /// returns [`None`] otherwise.
+    pub const fn new(year: u16) -> Option<Self> {
+        if year < 2015 {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Reads the year from a solution's path, e.g. `src/bin/2025-01.rs`.
+    /// Panics if the file name does not start with a year. Used by the [`solution!`](crate::solution) macro.
+    pub const fn from_bin_path(path: &str) -> Self {
+        let bytes = path.as_bytes();
+
+        let mut start = bytes.len();
+        while start > 0 && bytes[start - 1] != b'/' && bytes[start - 1] != b'\\' {
+            start -= 1;
+        }
+
+        assert!(
+            bytes.len() >= start + 4,
+            "expecting solution file name to start with a year, e.g. 2025-01.rs"
+        );
+
+        let mut year = 0;
+        let mut i = start;
+        while i < start + 4 {
+            assert!(
+                bytes[i].is_ascii_digit(),
+                "expecting solution file name to start with a year, e.g. 2025-01.rs"
+            );
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        match Self::new(year) {
+            Some(year) => year,
+            None => panic!("invalid year, expecting a value of 2015 or later"),
+        }
+    }
+
+    /// The year that commands operate on.
+    /// This is the year passed with `--year`, the year of the running solution or, if neither is set, `AOC_YEAR`.
+    pub fn current() -> Self {
+        CURRENT_YEAR.get().copied().unwrap_or_else(Self::configured)
+    }
+
+    /// The year configured with `AOC_YEAR`, or the latest event if it is not set.
+    pub fn configured() -> Self {
+        env::var("AOC_YEAR")
+            .ok()
+            .and_then(|year| year.parse().ok())
+            .unwrap_or(Self(LATEST_YEAR))
+    }
+
+    /// Overrides the year returned by [`Year::current`] for the rest of the process.
+    pub fn set_current(year: Self) {
+        let _ = CURRENT_YEAR.set(year);
+    }
+
+    /// The calendar of this year's event.
+    pub fn event(self) -> Event {
+        Event::for_year(self)
+    }
+
+    /// Directory that holds this year's inputs, examples, puzzles and timings, e.g. `data/2025`.
+    pub fn data_dir(self) -> PathBuf {
+        PathBuf::from("data").join(self.to_string())
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The calendar of an advent of code event.
+/// Events up to 2024 have 25 days, starting with 2025 events have 12 days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event {
+    last_day: Day,
+}
+
+impl Event {
+    pub fn for_year(year: Year) -> Self {
+        let last_day = if year.0 <= 2024 { 25 } else { 12 };
+        Self {
+            last_day: Day::new(last_day).unwrap(),
+        }
+    }
+
+    /// The event of the [current year](Year::current).
+    pub fn current() -> Self {
+        Self::for_year(Year::current())
+    }
+
+    /// The last day of the event.
+    pub fn last_day(self) -> Day {
+        self.last_day
+    }
+
+    /// Creates a [`Day`] from the provided value if it's part of this event, returns [`None`] otherwise.
+    pub fn day(self, day: u8) -> Option<Day> {
+        Day::new(day).filter(|day| self.contains(*day))
+    }
+
+    pub fn contains(self, day: Day) -> bool {
+        day <= self.last_day
+    }
+
+    /// An iterator that yields every day of this event.
+    pub fn days(self) -> AllDays {
+        AllDays::until(self.last_day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Event, Year};
+    use crate::day;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2024".parse::<Year>().unwrap(), Year(2024));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("24".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn reads_year_from_bin_path() {
+        assert_eq!(Year::from_bin_path("src/bin/2025-01.rs"), Year(2025));
+        assert_eq!(Year::from_bin_path("src\\bin\\2016-25.rs"), Year(2016));
+        assert_eq!(Year::from_bin_path("2017-03.rs"), Year(2017));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_bin_path_without_year() {
+        Year::from_bin_path("src/bin/01.rs");
+    }
+
+    #[test]
+    fn twelve_day_calendar() {
+        let event = Event::for_year(Year(2025));
+        assert_eq!(event.days().count(), 12);
+        assert_eq!(event.last_day(), day!(12));
+        assert_eq!(event.day(12), Some(day!(12)));
+        assert_eq!(event.day(13), None);
+        assert_eq!(Event::for_year(Year(2015)).day(25), Some(day!(25)));
+        assert_eq!(Event::for_year(Year(2015)).day(26), None);
+    }
+}